@@ -0,0 +1,81 @@
+//! Derive macros for the [deezer](https://docs.rs/deezer) crate.
+//!
+//! These are re-exported by the `deezer` crate and should be used from there.
+#![warn(missing_docs)]
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, Error, Type};
+
+/// Derives `DeezerPartial` and a `get_full(&client)` method for a subset of an api object.
+///
+/// The full api object is configured with the `#[deezer(full = Type)]` attribute,
+/// the struct is expected to contain the id of the full object in a field called `id`.
+///
+/// ```rust,ignore
+/// #[derive(Deserialize, Serialize, Debug, Clone, DeezerPartial)]
+/// #[deezer(full = Artist)]
+/// pub struct PartialArtist {
+///     pub id: ArtistId,
+///     pub name: String,
+/// }
+/// ```
+#[proc_macro_derive(DeezerPartial, attributes(deezer))]
+pub fn derive_deezer_partial(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let full = full_type(&input)?;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let full_name = quote!(#full).to_string();
+    let summary = format!(
+        " Returns the corresponding [`{}`], `None` when it no longer exists.",
+        full_name
+    );
+
+    Ok(quote! {
+        impl #impl_generics ::deezer::models::DeezerPartial for #name #ty_generics #where_clause {
+            type Full = #full;
+
+            fn id(&self) -> <Self::Full as ::deezer::models::DeezerObject>::Id {
+                self.id
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc = #summary]
+            ///
+            /// The object is fetched with the given client, using its configuration like the access token.
+            pub async fn get_full(
+                &self,
+                client: &::deezer::DeezerClient,
+            ) -> ::std::result::Result<::std::option::Option<#full>, ::deezer::DeezerError> {
+                <#full as ::deezer::models::DeezerObject>::get_with(self.id, client).await
+            }
+        }
+    })
+}
+
+fn full_type(input: &DeriveInput) -> syn::Result<Type> {
+    let mut full = None;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("deezer")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("full") {
+                full = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported deezer attribute"))
+            }
+        })?;
+    }
+
+    full.ok_or_else(|| Error::new_spanned(&input.ident, "missing #[deezer(full = Type)] attribute"))
+}
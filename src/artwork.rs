@@ -0,0 +1,151 @@
+//! Download and caching of covers and pictures.
+//!
+//! Images are identified by the `md5_image` of the api objects and fetched from deezer's image cdn
+//! in the requested size. With the `disk-cache` feature the images can also be stored in a directory,
+//! which survives restarts of the application.
+#![warn(missing_docs)]
+use std::collections::{HashMap, VecDeque};
+#[cfg(feature = "disk-cache")]
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use bytes::Bytes;
+
+use crate::models::image::cdn_image_url;
+use crate::{DeezerClient, Result};
+
+type ArtworkKey = (String, u32);
+
+/// Cache of downloaded images, keyed by their md5 and size.
+///
+/// The memory cache keeps the `capacity` most recently downloaded images.
+///
+/// # Examples
+/// ```rust
+/// # use deezer::{DeezerClient, DeezerError};
+/// # use deezer::artwork::ArtworkCache;
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), DeezerError> {
+/// let deezer = DeezerClient::new();
+/// let cache = ArtworkCache::new(100);
+///
+/// let album = deezer.album(302127).await?.unwrap();
+/// if let Some(md5) = &album.md5_image {
+///     let cover = cache.cover(&deezer, md5, 500).await?;
+///     // the second lookup is served from memory
+///     assert_eq!(cache.cover(&deezer, md5, 500).await?, cover);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ArtworkCache {
+    capacity: usize,
+    memory: Mutex<MemoryCache>,
+    #[cfg(feature = "disk-cache")]
+    directory: Option<PathBuf>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryCache {
+    images: HashMap<ArtworkKey, Bytes>,
+    insertion_order: VecDeque<ArtworkKey>,
+}
+
+impl ArtworkCache {
+    /// Creates a memory cache keeping up to `capacity` images.
+    pub fn new(capacity: usize) -> Self {
+        ArtworkCache {
+            capacity,
+            memory: Mutex::default(),
+            #[cfg(feature = "disk-cache")]
+            directory: None,
+        }
+    }
+
+    /// Creates a cache which additionally stores all images in `directory`.
+    ///
+    /// The directory is created when the first image is stored.
+    #[cfg(feature = "disk-cache")]
+    pub fn with_directory(capacity: usize, directory: impl Into<PathBuf>) -> Self {
+        ArtworkCache {
+            directory: Some(directory.into()),
+            ..ArtworkCache::new(capacity)
+        }
+    }
+
+    /// Returns the album cover with the given md5 in a size of `pixels`x`pixels`.
+    pub async fn cover(&self, client: &DeezerClient, md5: &str, pixels: u32) -> Result<Bytes> {
+        self.image(client, "cover", md5, pixels).await
+    }
+
+    /// Returns the image with the given md5 in a size of `pixels`x`pixels`.
+    ///
+    /// `kind` is the image type, e.g. `cover`, `artist` or `playlist`.
+    /// The image is downloaded when it's not cached yet, subject to the client's rate limiter.
+    pub async fn image(&self, client: &DeezerClient, kind: &str, md5: &str, pixels: u32) -> Result<Bytes> {
+        let key = (md5.to_string(), pixels);
+        if let Some(image) = self.memory.lock().unwrap().images.get(&key) {
+            return Ok(image.clone());
+        }
+        #[cfg(feature = "disk-cache")]
+        if let Some(image) = self.read_disk(&key).await {
+            self.insert_memory(key, image.clone());
+            return Ok(image);
+        }
+        let image = client.get_bytes(&cdn_image_url(kind, md5, pixels)).await?;
+        #[cfg(feature = "disk-cache")]
+        self.write_disk(&key, &image).await?;
+        self.insert_memory(key, image.clone());
+
+        Ok(image)
+    }
+
+    /// Removes all images from the memory cache.
+    pub fn clear(&self) {
+        let mut memory = self.memory.lock().unwrap();
+        memory.images.clear();
+        memory.insertion_order.clear();
+    }
+
+    fn insert_memory(&self, key: ArtworkKey, image: Bytes) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut memory = self.memory.lock().unwrap();
+        if memory.images.insert(key.clone(), image).is_none() {
+            memory.insertion_order.push_back(key);
+        }
+        while memory.insertion_order.len() > self.capacity {
+            if let Some(oldest) = memory.insertion_order.pop_front() {
+                memory.images.remove(&oldest);
+            }
+        }
+    }
+
+    #[cfg(feature = "disk-cache")]
+    fn disk_path(&self, (md5, pixels): &ArtworkKey) -> Option<PathBuf> {
+        let directory = self.directory.as_ref()?;
+
+        Some(directory.join(format!("{}-{}.jpg", md5, pixels)))
+    }
+
+    #[cfg(feature = "disk-cache")]
+    async fn read_disk(&self, key: &ArtworkKey) -> Option<Bytes> {
+        let path = self.disk_path(key)?;
+
+        tokio::fs::read(path).await.ok().map(Bytes::from)
+    }
+
+    #[cfg(feature = "disk-cache")]
+    async fn write_disk(&self, key: &ArtworkKey, image: &Bytes) -> Result<()> {
+        if let Some(path) = self.disk_path(key) {
+            if let Some(directory) = path.parent() {
+                tokio::fs::create_dir_all(directory).await?;
+            }
+            tokio::fs::write(path, image).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,408 @@
+//! The OAuth authorization code flow of Deezer
+//!
+//! 1. Redirect the user to the url built by [`AuthorizeUrl`]
+//! 2. Deezer redirects back to the `redirect_uri` with a `code` query parameter
+//! 3. Exchange the code for an [`AccessToken`] with [`exchange_code()`]
+//!
+//! The token of a [`DeezerClient`](crate::DeezerClient) is kept in a [`TokenStore`],
+//! which can be replaced to persist tokens or share them between clients.
+//!
+//! See the [Deezer Api Documentation](https://developers.deezer.com/api/oauth).
+#![warn(missing_docs)]
+use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{DeezerError, Result};
+
+const AUTHORIZE_URL: &str = "https://connect.deezer.com/oauth/auth.php";
+const ACCESS_TOKEN_URL: &str = "https://connect.deezer.com/oauth/access_token.php";
+
+/// A permission an application can request from the user.
+///
+/// [Deezer Api Documentation](https://developers.deezer.com/api/permissions)
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::auth::Permission;
+/// let permission: Permission = "manage_library".parse().unwrap();
+///
+/// assert_eq!(permission, Permission::ManageLibrary);
+/// assert_eq!(permission.to_string(), "manage_library");
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Permission {
+    /// Access users basic information
+    BasicAccess,
+
+    /// Get the user's email
+    Email,
+
+    /// Access user data any time, the access token doesn't expire
+    OfflineAccess,
+
+    /// Manage users' library, e.g. add favorites and create playlists
+    ManageLibrary,
+
+    /// Manage users' friends
+    ManageCommunity,
+
+    /// Delete library items
+    DeleteLibrary,
+
+    /// Allow the application to access the user's listening history
+    ListeningHistory,
+}
+
+impl Permission {
+    /// All permissions, in the order of the api documentation
+    pub const ALL: [Permission; 7] = [
+        Permission::BasicAccess,
+        Permission::Email,
+        Permission::OfflineAccess,
+        Permission::ManageLibrary,
+        Permission::ManageCommunity,
+        Permission::DeleteLibrary,
+        Permission::ListeningHistory,
+    ];
+
+    /// Returns the name of the permission as used by the api, e.g. `manage_library`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Permission::BasicAccess => "basic_access",
+            Permission::Email => "email",
+            Permission::OfflineAccess => "offline_access",
+            Permission::ManageLibrary => "manage_library",
+            Permission::ManageCommunity => "manage_community",
+            Permission::DeleteLibrary => "delete_library",
+            Permission::ListeningHistory => "listening_history",
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The error returned when parsing an unknown [`Permission`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("unknown permission `{0}`")]
+pub struct ParsePermissionError(String);
+
+impl FromStr for Permission {
+    type Err = ParsePermissionError;
+
+    fn from_str(name: &str) -> std::result::Result<Self, Self::Err> {
+        Permission::ALL
+            .iter()
+            .copied()
+            .find(|permission| permission.as_str() == name)
+            .ok_or_else(|| ParsePermissionError(name.to_string()))
+    }
+}
+
+/// Builder of the url users are sent to for authorizing an application.
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::auth::{AuthorizeUrl, Permission};
+/// let url = AuthorizeUrl::new("123456", "https://example.com/callback")
+///     .permission(Permission::BasicAccess)
+///     .permission(Permission::ManageLibrary)
+///     .state("c3f1a")
+///     .build();
+///
+/// assert_eq!(
+///     url.as_str(),
+///     "https://connect.deezer.com/oauth/auth.php?app_id=123456&redirect_uri=https%3A%2F%2Fexample.com%2Fcallback&perms=basic_access%2Cmanage_library&state=c3f1a"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizeUrl {
+    app_id: String,
+    redirect_uri: String,
+    permissions: Vec<Permission>,
+    state: Option<String>,
+}
+
+impl AuthorizeUrl {
+    /// Creates the url for the application with the given id, redirecting back to `redirect_uri`.
+    pub fn new(app_id: impl Into<String>, redirect_uri: impl Into<String>) -> Self {
+        AuthorizeUrl {
+            app_id: app_id.into(),
+            redirect_uri: redirect_uri.into(),
+            permissions: Vec::new(),
+            state: None,
+        }
+    }
+
+    /// Requests the given permission.
+    pub fn permission(mut self, permission: Permission) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
+    /// Returns the requested permissions, e.g. to record them with [`StoredToken::with_permissions()`].
+    pub fn permissions(&self) -> &[Permission] {
+        &self.permissions
+    }
+
+    /// Sets a value which is passed back to the `redirect_uri`, e.g. to protect against CSRF.
+    pub fn state(mut self, state: impl Into<String>) -> Self {
+        self.state = Some(state.into());
+        self
+    }
+
+    /// Returns the url to send the user to.
+    pub fn build(&self) -> Url {
+        let mut url = Url::parse(AUTHORIZE_URL).expect("authorize url is valid");
+        {
+            let mut query = url.query_pairs_mut();
+            query
+                .append_pair("app_id", &self.app_id)
+                .append_pair("redirect_uri", &self.redirect_uri)
+                .append_pair("perms", &self.permissions.iter().map(|permission| permission.as_str()).collect::<Vec<_>>().join(","));
+            if let Some(state) = &self.state {
+                query.append_pair("state", state);
+            }
+        }
+
+        url
+    }
+}
+
+/// An access token obtained with [`exchange_code()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AccessToken {
+    /// The token to authenticate requests with, see [`DeezerClient::with_access_token()`](crate::DeezerClient::with_access_token)
+    pub token: String,
+
+    /// The time after which the token expires, `None` for tokens which don't expire
+    pub expires_in: Option<Duration>,
+}
+
+#[derive(Deserialize)]
+struct AccessTokenResponse {
+    access_token: String,
+    /// Lifetime in seconds, `0` for tokens which don't expire
+    #[serde(default, deserialize_with = "crate::serde_helpers::lenient_code")]
+    expires: Option<u64>,
+}
+
+impl AccessTokenResponse {
+    /// Parses the body of a successful exchange, failed exchanges are answered with a plain text message like `wrong code`.
+    fn parse(body: &str) -> Result<AccessToken> {
+        let response: AccessTokenResponse =
+            serde_json::from_str(body).map_err(|_| DeezerError::AuthorizationFailed(body.trim().to_string()))?;
+        let expires_in = match response.expires {
+            Some(0) | None => None,
+            Some(seconds) => Some(Duration::from_secs(seconds)),
+        };
+
+        Ok(AccessToken {
+            token: response.access_token,
+            expires_in,
+        })
+    }
+}
+
+/// Exchanges the `code` passed to the redirect uri for an access token.
+///
+/// `secret` is the secret key of the application.
+pub async fn exchange_code(app_id: &str, secret: &str, code: &str) -> Result<AccessToken> {
+    let body = reqwest::Client::new()
+        .get(ACCESS_TOKEN_URL)
+        .query(&[("app_id", app_id), ("secret", secret), ("code", code), ("output", "json")])
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    AccessTokenResponse::parse(&body)
+}
+
+/// An access token together with the time it expires at.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StoredToken {
+    /// The token to authenticate requests with
+    pub token: String,
+
+    /// The time after which the token is no longer accepted, `None` when unknown or for tokens which don't expire
+    pub expires_at: Option<SystemTime>,
+
+    /// The permissions granted to the token, `None` when unknown
+    #[serde(default)]
+    pub permissions: Option<Vec<Permission>>,
+}
+
+impl StoredToken {
+    /// Creates a token without a known expiry.
+    pub fn new(token: impl Into<String>) -> Self {
+        StoredToken {
+            token: token.into(),
+            expires_at: None,
+            permissions: None,
+        }
+    }
+
+    /// Records the permissions granted to the token, allowing the client to reject requests
+    /// the token isn't permitted to perform without sending them.
+    pub fn with_permissions(mut self, permissions: impl IntoIterator<Item = Permission>) -> Self {
+        self.permissions = Some(permissions.into_iter().collect());
+        self
+    }
+
+    /// Returns whether the token has been granted the permission, `true` when the permissions are unknown.
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        self.permissions
+            .as_ref()
+            .is_none_or(|permissions| permissions.contains(&permission))
+    }
+
+    /// Returns whether the expiry of the token has passed.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= SystemTime::now())
+    }
+}
+
+impl From<String> for StoredToken {
+    fn from(token: String) -> Self {
+        StoredToken::new(token)
+    }
+}
+
+impl From<AccessToken> for StoredToken {
+    fn from(token: AccessToken) -> Self {
+        StoredToken {
+            token: token.token,
+            expires_at: token.expires_in.map(|expires_in| SystemTime::now() + expires_in),
+            permissions: None,
+        }
+    }
+}
+
+/// Storage of the access token a [`DeezerClient`](crate::DeezerClient) authenticates its requests with.
+///
+/// The store is consulted before every request and updated when the client
+/// [reauthenticates](crate::DeezerClient::with_reauthentication).
+///
+/// # Examples
+///
+/// Sharing the token between two clients:
+///
+/// ```rust
+/// # use std::sync::Arc;
+/// # use deezer::DeezerClient;
+/// # use deezer::auth::{MemoryTokenStore, StoredToken, TokenStore};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), deezer::DeezerError> {
+/// let store = Arc::new(MemoryTokenStore::default());
+/// let first = DeezerClient::new().with_token_store(store.clone());
+/// let second = DeezerClient::new().with_token_store(store.clone());
+///
+/// store.set(StoredToken::new("access-token")).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[async_trait]
+pub trait TokenStore: fmt::Debug + Send + Sync {
+    /// Returns the current token, `None` when unauthenticated.
+    async fn get(&self) -> Result<Option<StoredToken>>;
+
+    /// Replaces the current token.
+    async fn set(&self, token: StoredToken) -> Result<()>;
+
+    /// Removes the current token.
+    async fn clear(&self) -> Result<()>;
+}
+
+#[async_trait]
+impl<T: TokenStore + ?Sized> TokenStore for std::sync::Arc<T> {
+    async fn get(&self) -> Result<Option<StoredToken>> {
+        (**self).get().await
+    }
+
+    async fn set(&self, token: StoredToken) -> Result<()> {
+        (**self).set(token).await
+    }
+
+    async fn clear(&self) -> Result<()> {
+        (**self).clear().await
+    }
+}
+
+/// Keeps the token in memory, the default [`TokenStore`] of a [`DeezerClient`](crate::DeezerClient).
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+    token: RwLock<Option<StoredToken>>,
+}
+
+impl MemoryTokenStore {
+    /// Creates a store containing the given token.
+    pub fn new(token: StoredToken) -> Self {
+        MemoryTokenStore {
+            token: RwLock::new(Some(token)),
+        }
+    }
+}
+
+#[async_trait]
+impl TokenStore for MemoryTokenStore {
+    async fn get(&self) -> Result<Option<StoredToken>> {
+        Ok(self.token.read().unwrap().clone())
+    }
+
+    async fn set(&self, token: StoredToken) -> Result<()> {
+        *self.token.write().unwrap() = Some(token);
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<()> {
+        *self.token.write().unwrap() = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expires_in(body: &str) -> Option<Duration> {
+        AccessTokenResponse::parse(body).unwrap().expires_in
+    }
+
+    #[test]
+    fn lifetimes_are_read_as_seconds() {
+        assert_eq!(expires_in(r#"{"access_token": "token", "expires": 3600}"#), Some(Duration::from_secs(3600)));
+        assert_eq!(expires_in(r#"{"access_token": "token", "expires": "3600"}"#), Some(Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn tokens_without_lifetime_do_not_expire() {
+        assert_eq!(expires_in(r#"{"access_token": "token", "expires": 0}"#), None);
+        assert_eq!(expires_in(r#"{"access_token": "token", "expires": null}"#), None);
+        assert_eq!(expires_in(r#"{"access_token": "token"}"#), None);
+    }
+
+    #[test]
+    fn failed_exchanges_keep_the_message() {
+        let result = AccessTokenResponse::parse("wrong code\n");
+
+        assert!(matches!(result, Err(DeezerError::AuthorizationFailed(message)) if message == "wrong code"));
+    }
+}
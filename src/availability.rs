@@ -0,0 +1,128 @@
+//! Checking which tracks can be played in a country.
+//!
+//! Useful before exporting or playing a playlist abroad: tracks which aren't available are replaced
+//! by their alternative version where possible, the rest is reported as unavailable.
+#![warn(missing_docs)]
+use crate::models::{PartialTrack, Playlist, Track, TrackId};
+use crate::{fetch, DeezerClient, Result};
+
+/// The availability of a list of tracks in one country.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[non_exhaustive]
+pub struct AvailabilityReport {
+    /// The tracks available in the country, in the order of the checked tracks.
+    ///
+    /// Unavailable tracks with an available alternative are replaced by the alternative.
+    pub available: Vec<Track>,
+
+    /// Unavailable tracks which were replaced by their alternative, as `(original, alternative)`
+    pub replaced: Vec<(TrackId, TrackId)>,
+
+    /// Tracks which are neither available nor have an available alternative,
+    /// including ids which are unknown to deezer, e.g. because the track has been removed
+    pub unavailable: Vec<TrackId>,
+}
+
+impl AvailabilityReport {
+    /// Returns `true` when every checked track can be played, possibly in its alternative version.
+    pub fn is_complete(&self) -> bool {
+        self.unavailable.is_empty()
+    }
+}
+
+/// Checks the availability of the tracks with the given ids in `country`, an ISO 3166 country code like a [`CountryCode`](crate::models::CountryCode).
+///
+/// Keeps at most `concurrency` requests in flight. Tracks deezer doesn't know (anymore) are reported
+/// as [`unavailable`](AvailabilityReport::unavailable) instead of failing the whole check.
+///
+/// # Examples
+/// ```rust
+/// # use deezer::{availability, DeezerClient, DeezerError};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), DeezerError> {
+/// let deezer = DeezerClient::new();
+/// let report = availability::check(&deezer, vec![3135556, 3135553], "DE", 4).await?;
+/// for track in report.unavailable {
+///     println!("track {} can't be played in Germany", track);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn check<I>(client: &DeezerClient, tracks: I, country: impl AsRef<str>, concurrency: usize) -> Result<AvailabilityReport>
+where
+    I: IntoIterator,
+    I::Item: Into<TrackId>,
+{
+    let country = country.as_ref();
+    let ids: Vec<TrackId> = tracks.into_iter().map(Into::into).collect();
+    let resolved = fetch::batched(ids.iter().copied(), concurrency, |id| resolve(client, id, country)).await?;
+
+    let mut report = AvailabilityReport::default();
+    for (id, track) in ids.into_iter().zip(resolved) {
+        match track {
+            Some(track) => {
+                if track.id != id {
+                    report.replaced.push((id, track.id));
+                }
+                report.available.push(track);
+            }
+            None => report.unavailable.push(id),
+        }
+    }
+
+    Ok(report)
+}
+
+/// Checks the availability of the tracks with the given ids in the country the api is accessed from,
+/// or the country the client is [configured](DeezerClient::with_country) for.
+///
+/// See [`check()`] and [`DeezerClient::current_country()`].
+/// Returns `None` when deezer doesn't report a valid country.
+pub async fn check_in_current_country<I>(client: &DeezerClient, tracks: I,
+                                         concurrency: usize) -> Result<Option<AvailabilityReport>>
+where
+    I: IntoIterator,
+    I::Item: Into<TrackId>,
+{
+    match client.current_country().await? {
+        Some(country) => check(client, tracks, country, concurrency).await.map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Checks the availability of the tracks of a playlist, see [`check()`].
+pub async fn check_tracks(client: &DeezerClient, tracks: &[PartialTrack], country: impl AsRef<str>,
+                          concurrency: usize) -> Result<AvailabilityReport> {
+    check(client, tracks.iter().map(|track| track.id), country, concurrency).await
+}
+
+/// Checks the availability of all tracks of `playlist`, see [`check()`].
+pub async fn check_playlist(client: &DeezerClient, playlist: &Playlist, country: impl AsRef<str>,
+                            concurrency: usize) -> Result<AvailabilityReport> {
+    check_tracks(client, &playlist.tracks, country, concurrency).await
+}
+
+/// Checks the availability of all tracks of `playlist` in the current country, see [`check_in_current_country()`].
+pub async fn check_playlist_in_current_country(client: &DeezerClient, playlist: &Playlist,
+                                               concurrency: usize) -> Result<Option<AvailabilityReport>> {
+    check_in_current_country(client, playlist.tracks.iter().map(|track| track.id), concurrency).await
+}
+
+/// Returns the track or its alternative, whichever is available in `country`.
+///
+/// `None` when neither is available, which includes tracks and alternatives deezer answers with `no data`.
+async fn resolve(client: &DeezerClient, id: TrackId, country: &str) -> Result<Option<Track>> {
+    let track = match client.track(id).await? {
+        Some(track) => track,
+        None => return Ok(None),
+    };
+    if track.is_available_in(country) {
+        return Ok(Some(track));
+    }
+    let alternative = match &track.alternative {
+        Some(alternative) => alternative.get_full(client).await?,
+        None => None,
+    };
+
+    Ok(alternative.filter(|track| track.is_available_in(country)))
+}
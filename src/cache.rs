@@ -0,0 +1,47 @@
+//! Caching of api responses
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use bytes::Bytes;
+use reqwest::Url;
+
+/// Keeps the bodies of the `capacity` most recently fetched api objects, keyed by their url.
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    capacity: usize,
+    entries: Mutex<CacheEntries>,
+}
+
+#[derive(Debug, Default)]
+struct CacheEntries {
+    bodies: HashMap<Url, Bytes>,
+    insertion_order: VecDeque<Url>,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        ResponseCache {
+            capacity,
+            entries: Mutex::default(),
+        }
+    }
+
+    pub(crate) fn get(&self, url: &Url) -> Option<Bytes> {
+        self.entries.lock().unwrap().bodies.get(url).cloned()
+    }
+
+    pub(crate) fn insert(&self, url: Url, body: Bytes) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if entries.bodies.insert(url.clone(), body).is_none() {
+            entries.insertion_order.push_back(url);
+        }
+        while entries.insertion_order.len() > self.capacity {
+            if let Some(oldest) = entries.insertion_order.pop_front() {
+                entries.bodies.remove(&oldest);
+            }
+        }
+    }
+}
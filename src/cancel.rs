@@ -0,0 +1,86 @@
+//! Cancellation of long running operations, like fetching all pages of a list or a library sync.
+//!
+//! All operations of this crate stop their pending requests when their future is dropped,
+//! so they can also be cancelled with `tokio::select!` or a timeout. A [`CancellationToken`]
+//! additionally allows cancelling them from another task, e.g. a cancel button of a UI.
+#![warn(missing_docs)]
+use std::future::Future;
+use std::sync::Arc;
+
+use futures::future::{self, Either};
+use tokio::sync::watch;
+
+use crate::{DeezerError, Result};
+
+/// Cancels all operations run with it or one of its clones.
+///
+/// # Examples
+/// ```rust
+/// # use deezer::{DeezerClient, DeezerError};
+/// # use deezer::cancel::CancellationToken;
+/// # #[tokio::main]
+/// # async fn main() {
+/// let deezer = DeezerClient::new();
+/// let token = CancellationToken::new();
+///
+/// token.cancel();
+/// let result = token.run(deezer.user_favorite_tracks(2529)).await;
+///
+/// assert!(matches!(result, Err(DeezerError::Cancelled)));
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    cancelled: Arc<watch::Sender<bool>>,
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CancellationToken {
+    /// Creates a token which is not cancelled yet.
+    pub fn new() -> Self {
+        CancellationToken {
+            cancelled: Arc::new(watch::Sender::new(false)),
+        }
+    }
+
+    /// Cancels all operations run with this token, including the ones started later.
+    pub fn cancel(&self) {
+        self.cancelled.send_replace(true);
+    }
+
+    /// Returns `true` when the token was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancelled.borrow()
+    }
+
+    /// Completes once the token is cancelled.
+    pub async fn cancelled(&self) {
+        let mut cancelled = self.cancelled.subscribe();
+        // the sender lives as long as self, so waiting can't fail
+        let _ = cancelled.wait_for(|cancelled| *cancelled).await;
+    }
+
+    /// Runs `operation` until it completes or the token is cancelled.
+    ///
+    /// On cancellation the operation is dropped, aborting its pending requests,
+    /// and [`DeezerError::Cancelled`] is returned.
+    pub async fn run<F, T>(&self, operation: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        if self.is_cancelled() {
+            return Err(DeezerError::Cancelled);
+        }
+        let cancelled = self.cancelled();
+        futures::pin_mut!(operation, cancelled);
+        match future::select(operation, cancelled).await {
+            Either::Left((result, _)) => result,
+            Either::Right(((), _)) => Err(DeezerError::Cancelled),
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//! The reference lists of the api, which nearly every application needs at startup.
+#![warn(missing_docs)]
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{Editorial, Genre, GenreId, Radio};
+use crate::{snapshot, Result};
+
+/// All genres, editorials and radios of the api, created by [`DeezerClient::bootstrap()`](crate::DeezerClient::bootstrap).
+///
+/// The lists keep the order of the api, lookups by id use an index.
+///
+/// # Examples
+/// ```rust
+/// # use deezer::catalog::Catalog;
+/// # use deezer::models::Genre;
+/// let catalog = Catalog::new(vec![Genre::new(0, "All"), Genre::new(132, "Pop")], Vec::new(), Vec::new());
+///
+/// assert_eq!(catalog.genre(132).map(|genre| genre.name.as_str()), Some("Pop"));
+/// assert_eq!(catalog.genre(152), None);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(from = "CatalogLists", into = "CatalogLists")]
+pub struct Catalog {
+    genres: Vec<Genre>,
+    editorials: Vec<Editorial>,
+    radios: Vec<Radio>,
+    genre_index: HashMap<GenreId, usize>,
+    editorial_index: HashMap<GenreId, usize>,
+    radio_index: HashMap<u64, usize>,
+}
+
+impl Catalog {
+    /// Creates a catalog from the given lists, indexing them by id.
+    pub fn new(genres: Vec<Genre>, editorials: Vec<Editorial>, radios: Vec<Radio>) -> Self {
+        Catalog {
+            genre_index: index(&genres, |genre| genre.id),
+            editorial_index: index(&editorials, |editorial| editorial.id),
+            radio_index: index(&radios, |radio| radio.id),
+            genres,
+            editorials,
+            radios,
+        }
+    }
+
+    /// Stores the catalog in a file, see [`snapshot::save()`].
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        snapshot::save(path, self)
+    }
+
+    /// Loads a catalog stored with [`Catalog::save()`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::catalog::Catalog;
+    /// # use deezer::models::Radio;
+    /// # fn main() -> Result<(), deezer::DeezerError> {
+    /// let path = std::env::temp_dir().join("deezer-catalog.json");
+    /// let catalog = Catalog::new(Vec::new(), Vec::new(), vec![Radio::new(6, "Rock")]);
+    ///
+    /// catalog.save(&path)?;
+    /// let loaded = Catalog::load(&path)?;
+    ///
+    /// assert_eq!(loaded.radio(6), catalog.radio(6));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        snapshot::load(path)
+    }
+
+    /// All genres
+    pub fn genres(&self) -> &[Genre] {
+        &self.genres
+    }
+
+    /// All editorials
+    pub fn editorials(&self) -> &[Editorial] {
+        &self.editorials
+    }
+
+    /// All radios
+    pub fn radios(&self) -> &[Radio] {
+        &self.radios
+    }
+
+    /// Returns the genre with the given id
+    pub fn genre(&self, id: impl Into<GenreId>) -> Option<&Genre> {
+        self.genre_index.get(&id.into()).map(|&index| &self.genres[index])
+    }
+
+    /// Returns the editorial with the given id
+    pub fn editorial(&self, id: impl Into<GenreId>) -> Option<&Editorial> {
+        self.editorial_index.get(&id.into()).map(|&index| &self.editorials[index])
+    }
+
+    /// Returns the radio with the given id
+    pub fn radio(&self, id: u64) -> Option<&Radio> {
+        self.radio_index.get(&id).map(|&index| &self.radios[index])
+    }
+}
+
+/// The serialized form of a [`Catalog`], the indices are rebuilt when loading.
+#[derive(Deserialize, Serialize)]
+struct CatalogLists {
+    genres: Vec<Genre>,
+    editorials: Vec<Editorial>,
+    radios: Vec<Radio>,
+}
+
+impl From<CatalogLists> for Catalog {
+    fn from(lists: CatalogLists) -> Self {
+        Catalog::new(lists.genres, lists.editorials, lists.radios)
+    }
+}
+
+impl From<Catalog> for CatalogLists {
+    fn from(catalog: Catalog) -> Self {
+        CatalogLists {
+            genres: catalog.genres,
+            editorials: catalog.editorials,
+            radios: catalog.radios,
+        }
+    }
+}
+
+fn index<T, K: Hash + Eq>(items: &[T], id: impl Fn(&T) -> K) -> HashMap<K, usize> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(position, item)| (id(item), position))
+        .collect()
+}
@@ -0,0 +1,102 @@
+//! Caching of the charts, which only change a few times a day.
+#![warn(missing_docs)]
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::models::Chart;
+use crate::{DeezerClient, Result};
+
+/// Serves the last fetched [`Chart`] as long as it's younger than `max_age`.
+///
+/// Once the chart is outdated it's still returned while a fresh one is fetched in the background,
+/// so only the very first lookup has to wait for the api.
+/// The background refresh is spawned on the current tokio runtime.
+///
+/// # Examples
+/// ```rust
+/// # use std::time::Duration;
+/// # use deezer::{DeezerClient, DeezerError};
+/// # use deezer::charts::CachedCharts;
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), DeezerError> {
+/// let charts = CachedCharts::new(DeezerClient::new(), Duration::from_secs(15 * 60));
+///
+/// let chart = charts.get().await?;
+/// // served from the cache
+/// let cached = charts.get().await?;
+/// assert_eq!(chart, cached);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachedCharts {
+    client: DeezerClient,
+    max_age: Duration,
+    state: Arc<Mutex<CacheState>>,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    chart: Option<(Arc<Chart>, Instant)>,
+    refreshing: bool,
+}
+
+impl CachedCharts {
+    /// Creates an empty cache, considering fetched charts fresh for `max_age`.
+    pub fn new(client: DeezerClient, max_age: Duration) -> Self {
+        CachedCharts {
+            client,
+            max_age,
+            state: Arc::default(),
+        }
+    }
+
+    /// Returns the cached chart, fetching it when nothing is cached yet.
+    ///
+    /// An outdated chart is returned immediately while it's refreshed in the background.
+    pub async fn get(&self) -> Result<Arc<Chart>> {
+        let cached = {
+            let mut state = self.state.lock().unwrap();
+            match state.chart.clone() {
+                Some((chart, fetched_at)) => {
+                    if fetched_at.elapsed() >= self.max_age && !state.refreshing {
+                        state.refreshing = true;
+                        self.spawn_refresh();
+                    }
+                    Some(chart)
+                }
+                None => None,
+            }
+        };
+
+        match cached {
+            Some(chart) => Ok(chart),
+            None => self.refresh().await,
+        }
+    }
+
+    /// Fetches the current chart, replacing the cached one.
+    pub async fn refresh(&self) -> Result<Arc<Chart>> {
+        let result = self.client.charts().await;
+        let mut state = self.state.lock().unwrap();
+        state.refreshing = false;
+        let chart = Arc::new(result?);
+        state.chart = Some((Arc::clone(&chart), Instant::now()));
+
+        Ok(chart)
+    }
+
+    /// Drops the cached chart, the next lookup will wait for a fresh one.
+    pub fn invalidate(&self) {
+        self.state.lock().unwrap().chart = None;
+    }
+
+    fn spawn_refresh(&self) {
+        let cache = self.clone();
+        tokio::spawn(async move {
+            if let Err(err) = cache.refresh().await {
+                log::warn!("Refreshing the charts failed: {}", err);
+            }
+        });
+    }
+}
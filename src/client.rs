@@ -1,19 +1,153 @@
 #![warn(missing_docs)]
 
-use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Instant, SystemTime};
 
-use reqwest::StatusCode;
-use serde::de::DeserializeOwned;
+use bytes::Bytes;
+use futures::future::{BoxFuture, FutureExt, TryFutureExt};
+use futures::stream::{self, Stream, TryStreamExt};
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::multipart::{Form, Part};
+use reqwest::{IntoUrl, Method, StatusCode, Url};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::Deserialize;
 
+use crate::auth::{AccessToken, MemoryTokenStore, Permission, StoredToken, TokenStore};
+use crate::cache::ResponseCache;
+use crate::catalog::Catalog;
+use crate::fetch::{self, BatchResult};
+use crate::journal::{Journal, JournalEntry};
+use crate::logging::{self, RequestLog};
+use crate::me::MeClient;
 use crate::models::*;
-use crate::Result;
+use crate::rate_limit::RateLimiter;
+use crate::redirect::RedirectPolicy;
+use crate::request::RequestBuilder;
+use crate::search::SearchQuery;
+use crate::{DeezerError, Params, Result};
 
-const BASE_URL: &str = "https://api.deezer.com";
+/// Default root of the api, see [`DeezerClient::with_base_url()`]
+const BASE_URL: &str = "https://api.deezer.com/";
+
+/// Default host accepting uploads like playlist pictures, see [`DeezerClient::with_upload_url()`]
+const UPLOAD_URL: &str = "https://upload.deezer.com/";
+
+/// Maximum number of requests [`DeezerClient::warm_cache()`] keeps in flight.
+const WARM_UP_CONCURRENCY: usize = 8;
+
+/// Error code of the `no data` error deezer answers lookups of unknown objects with.
+const DATA_NOT_FOUND: u32 = 800;
+
+/// Only the contributors of a track, the other fields are skipped while parsing.
+#[derive(Deserialize)]
+struct TrackContributors {
+    #[serde(default)]
+    contributors: Vec<ContributorArtist>,
+}
+
+/// Only the checksum of a list, the items are skipped while parsing.
+#[derive(Deserialize)]
+struct ListChecksum {
+    #[serde(default)]
+    checksum: Option<String>,
+}
+
+/// Error object of a failed api request, e.g. `{"error": {"type": "OAuthException", "message": "…", "code": 300}}`.
+#[derive(Deserialize)]
+struct ApiErrorBody {
+    error: ApiError,
+}
+
+#[derive(Deserialize)]
+struct ApiError {
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    code: u32,
+}
+
+impl ApiError {
+    /// Parses the error object of a response body, `None` for responses without one.
+    fn from_body(body: &[u8]) -> Option<ApiError> {
+        serde_json::from_slice::<ApiErrorBody>(body).ok().map(|body| body.error)
+    }
+
+    /// Returns the matching [`DeezerError`] for errors caused by the access token.
+    fn into_token_error(self) -> Option<DeezerError> {
+        match self.code {
+            // deezer reports expired tokens with the same code as invalid ones
+            300 if self.message.to_lowercase().contains("expired") => Some(DeezerError::TokenExpired(self.message)),
+            300 => Some(DeezerError::InvalidToken(self.message)),
+            200 => Some(DeezerError::InsufficientPermission(self.message)),
+            _ => None,
+        }
+    }
+}
+
+impl From<ApiError> for DeezerError {
+    fn from(error: ApiError) -> Self {
+        DeezerError::ApiError {
+            code: error.code,
+            message: error.message,
+        }
+    }
+}
+
+/// Appends a `/` to the path of the url, so relative paths are joined onto it instead of replacing its last segment.
+fn with_trailing_slash(mut url: Url) -> Url {
+    if !url.path().ends_with('/') {
+        let path = format!("{}/", url.path());
+        url.set_path(&path);
+    }
+
+    url
+}
 
 /// Entrypoint to interact with all deezer apis
 #[derive(Debug, Clone)]
 pub struct DeezerClient {
     client: reqwest::Client,
+    base_url: Url,
+    upload_url: Url,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    correlation_id: Option<Arc<CorrelationId>>,
+    request_log: Option<Arc<RequestLog>>,
+    journal: Option<Arc<dyn Journal>>,
+    token_store: Arc<dyn TokenStore>,
+    reauthentication: Option<Arc<Reauthentication>>,
+    cache: Option<Arc<ResponseCache>>,
+    country: Option<CountryCode>,
+}
+
+/// Obtains a new access token after the api rejected the current one.
+struct Reauthentication(Box<dyn Fn() -> BoxFuture<'static, Result<StoredToken>> + Send + Sync>);
+
+impl fmt::Debug for Reauthentication {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Reauthentication")
+    }
+}
+
+/// Generates the value of a tracing header for every api request.
+struct CorrelationId {
+    header: HeaderName,
+    generate: Box<dyn Fn() -> HeaderValue + Send + Sync>,
+}
+
+impl fmt::Debug for CorrelationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CorrelationId")
+            .field("header", &self.header)
+            .finish()
+    }
+}
+
+impl Default for DeezerClient {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl DeezerClient {
@@ -21,14 +155,244 @@ impl DeezerClient {
     pub fn new() -> Self {
         DeezerClient {
             client: reqwest::Client::new(),
+            base_url: Url::parse(BASE_URL).expect("base url is valid"),
+            upload_url: Url::parse(UPLOAD_URL).expect("upload url is valid"),
+            rate_limiter: None,
+            correlation_id: None,
+            request_log: None,
+            journal: None,
+            token_store: Arc::new(MemoryTokenStore::default()),
+            reauthentication: None,
+            cache: None,
+            country: None,
         }
     }
 
+    /// Limits the requests to the api issued by this client and its clones.
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use deezer::DeezerClient;
+    /// # use deezer::rate_limit::RateLimiter;
+    /// // Deezer allows 50 requests per 5 seconds
+    /// let deezer = DeezerClient::new().with_rate_limiter(RateLimiter::new(50, Duration::from_secs(5)));
+    /// ```
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(Arc::new(rate_limiter));
+        self
+    }
+
+    /// Sets which redirects are followed by the requests of this client, by default up to 10 redirects to any host.
+    ///
+    /// See [`RedirectPolicy`] for the available restrictions.
+    pub fn with_redirect_policy(mut self, policy: RedirectPolicy) -> Self {
+        self.client = reqwest::Client::builder()
+            .redirect(policy.into_policy())
+            .build()
+            .expect("http client can be built");
+        self
+    }
+
+    /// Caches the `capacity` most recently fetched api objects, like albums or tracks, in memory.
+    ///
+    /// The cache is shared with the clones of this client.
+    pub fn with_cache(mut self, capacity: usize) -> Self {
+        self.cache = Some(Arc::new(ResponseCache::new(capacity)));
+        self
+    }
+
+    /// Sends the api requests of this client to the given root instead of `https://api.deezer.com`,
+    /// e.g. to a caching proxy or a mock server in tests.
+    ///
+    /// Paths of api requests are appended to the path of the url:
+    ///
+    /// ```rust
+    /// # use deezer::DeezerClient;
+    /// let url = reqwest::Url::parse("http://localhost:8080/deezer").unwrap();
+    /// let deezer = DeezerClient::new().with_base_url(url);
+    /// ```
+    pub fn with_base_url(mut self, url: Url) -> Self {
+        self.base_url = with_trailing_slash(url);
+        self
+    }
+
+    /// Sends uploads like [playlist pictures](DeezerClient::set_playlist_picture) to the given host
+    /// instead of `https://upload.deezer.com`, see [`with_base_url()`](DeezerClient::with_base_url).
+    pub fn with_upload_url(mut self, url: Url) -> Self {
+        self.upload_url = with_trailing_slash(url);
+        self
+    }
+
+    /// Assumes the users of this client are in the given country, instead of the country deezer detects.
+    ///
+    /// Used by [`current_country()`](DeezerClient::current_country) and thereby by the
+    /// [availability](crate::availability) helpers, e.g. for servers evaluating the availability
+    /// for users in other regions.
+    pub fn with_country(mut self, country: CountryCode) -> Self {
+        self.country = Some(country);
+        self
+    }
+
+    /// Authenticates all api requests of this client and its clones with the given OAuth access token.
+    ///
+    /// Replaces the [token store](DeezerClient::with_token_store) with an in-memory store containing the token.
+    pub fn with_access_token(self, token: impl Into<String>) -> Self {
+        self.with_token_store(MemoryTokenStore::new(StoredToken::new(token)))
+    }
+
+    /// Authenticates all api requests of this client and its clones with a token obtained via [`auth::exchange_code()`](crate::auth::exchange_code).
+    ///
+    /// Once the token expires a registered [reauthentication](DeezerClient::with_reauthentication)
+    /// callback is invoked before sending the next request, instead of waiting for the api to reject it.
+    ///
+    /// Replaces the [token store](DeezerClient::with_token_store) with an in-memory store containing the token.
+    pub fn with_oauth_token(self, token: AccessToken) -> Self {
+        self.with_token_store(MemoryTokenStore::new(token.into()))
+    }
+
+    /// Reads the access token from the given store before every request, e.g. to persist it in a keyring
+    /// or to share it between clients.
+    ///
+    /// Tokens obtained by [reauthentication](DeezerClient::with_reauthentication) are written to the store.
+    pub fn with_token_store(mut self, store: impl TokenStore + 'static) -> Self {
+        self.token_store = Arc::new(store);
+        self
+    }
+
+    /// Registers a callback obtaining a new access token when the api rejects the current one.
+    ///
+    /// The callback is invoked once for a request failing with an invalid or expired token,
+    /// afterwards the request is sent again with the new token.
+    /// Tokens with a known expiry are replaced before sending requests with them, see [`DeezerClient::with_oauth_token()`].
+    /// The new token is used for all following requests of this client and its clones.
+    ///
+    /// The callback can return the token as `String`, as [`AccessToken`] to record its expiry or as [`StoredToken`].
+    /// Unless the new token records its permissions, the permissions of the replaced token are carried over.
+    ///
+    /// ```rust
+    /// # use deezer::DeezerClient;
+    /// # async fn refresh_token() -> Result<String, deezer::DeezerError> { Ok(String::new()) }
+    /// let deezer = DeezerClient::new()
+    ///     .with_access_token("expired-token")
+    ///     .with_reauthentication(|| refresh_token());
+    /// ```
+    pub fn with_reauthentication<F, Fut, T>(mut self, reauthenticate: F) -> Self
+        where
+            F: Fn() -> Fut + Send + Sync + 'static,
+            Fut: Future<Output = Result<T>> + Send + 'static,
+            T: Into<StoredToken> + Send + 'static,
+    {
+        self.reauthentication = Some(Arc::new(Reauthentication(Box::new(move || {
+            reauthenticate().map_ok(Into::into).boxed()
+        }))));
+        self
+    }
+
+    /// Sends a header generated by `generate` with every api request,
+    /// e.g. to trace requests through proxies and match them with the application logs.
+    ///
+    /// ```rust
+    /// # use std::sync::atomic::{AtomicU64, Ordering};
+    /// # use deezer::DeezerClient;
+    /// # use reqwest::header::{HeaderName, HeaderValue};
+    /// let counter = AtomicU64::new(0);
+    /// let deezer = DeezerClient::new().with_correlation_id(HeaderName::from_static("x-request-id"), move || {
+    ///     HeaderValue::from(counter.fetch_add(1, Ordering::Relaxed))
+    /// });
+    /// ```
+    pub fn with_correlation_id<F>(mut self, header: HeaderName, generate: F) -> Self
+        where
+            F: Fn() -> HeaderValue + Send + Sync + 'static,
+    {
+        self.correlation_id = Some(Arc::new(CorrelationId {
+            header,
+            generate: Box::new(generate),
+        }));
+        self
+    }
+
+    /// Logs every request of this client and its clones with the `log` crate at the given level.
+    ///
+    /// Each request is logged as one line of `key=value` pairs with the method, url, status code and duration,
+    /// using the target `deezer::request`. Access tokens in the urls are redacted.
+    ///
+    /// ```rust
+    /// # use deezer::DeezerClient;
+    /// let deezer = DeezerClient::new().with_request_logging(log::Level::Info);
+    /// ```
+    pub fn with_request_logging(mut self, level: log::Level) -> Self {
+        self.request_log = Some(Arc::new(RequestLog::new(level)));
+        self
+    }
+
+    /// Records every request of this client and its clones in the given [`Journal`], e.g. for audits.
+    ///
+    /// The recorded parameters don't contain the access token.
+    pub fn with_journal(mut self, journal: impl Journal + 'static) -> Self {
+        self.journal = Some(Arc::new(journal));
+        self
+    }
+
+    /// Fails with [`DeezerError::MissingPermission`] when the stored access token is known
+    /// not to have been granted the permission, see [`StoredToken::with_permissions()`].
+    ///
+    /// Requests modifying the library of the user check their permission before being sent,
+    /// requests to unmodelled endpoints can use this to do the same.
+    ///
+    /// ```rust
+    /// # use deezer::DeezerClient;
+    /// # use deezer::auth::{MemoryTokenStore, Permission, StoredToken};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let token = StoredToken::new("access-token").with_permissions(vec![Permission::BasicAccess]);
+    /// let deezer = DeezerClient::new().with_token_store(MemoryTokenStore::new(token));
+    ///
+    /// assert!(deezer.ensure_permission(Permission::BasicAccess).await.is_ok());
+    /// assert!(deezer.ensure_permission(Permission::ManageLibrary).await.is_err());
+    /// # }
+    /// ```
+    pub async fn ensure_permission(&self, permission: Permission) -> Result<()> {
+        match self.token_store.get().await? {
+            Some(token) if !token.has_permission(permission) => Err(DeezerError::MissingPermission(permission)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Starts a request to an api endpoint which is not modelled by this crate.
+    ///
+    /// `path` is relative to the api root, e.g. `user/me/playlists`, see [`with_base_url()`](DeezerClient::with_base_url).
+    /// The request is subject to the client's rate limit.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError, Method};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let top: serde_json::Value = deezer
+    ///     .request(Method::GET, "artist/27/top")
+    ///     .query("limit", 3)
+    ///     .json()
+    ///     .await?;
+    /// # assert!(top["data"].is_array());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn request(&self, method: Method, path: &str) -> RequestBuilder<'_> {
+        let request = match self.base_url.join(path.trim_start_matches('/')) {
+            Ok(url) => self.client.request(method, url),
+            // the relative path fails to build, surfacing the error when the request is sent
+            Err(_) => self.client.request(method, path),
+        };
+
+        RequestBuilder::new(self, request)
+    }
+
     /// Returns the [`Album`] with the given id.
     ///
     /// [Deezer Api Documentation](https://developers.deezer.com/api/album)
-    pub async fn album(&self, id: u64) -> Result<Option<Album>> {
-        self.get_entity(id).await
+    pub async fn album(&self, id: impl IntoDeezerId<AlbumId>) -> Result<Option<Album>> {
+        self.get_entity(id.into_deezer_id()?).await
     }
 
     /// Returns the [`Album`] with the given upc.
@@ -38,19 +402,137 @@ impl DeezerClient {
         self.get_entity_by_upc(upc).await
     }
 
+    /// Returns the genres of the [`Album`] with the given id.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/album/genres)
+    pub async fn album_genres(&self, id: impl Into<AlbumId>) -> Result<Vec<AlbumGenre>> {
+        let url = self.api_url(&["album", &id.into().to_string(), "genres"]);
+
+        self.get_all_pages(url).await
+    }
+
+    /// Returns the number of fans of the album with the given id, without fetching the fans.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let fans = deezer.album_fans_count(302127).await?;
+    /// # assert!(fans > 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/album/fans)
+    pub async fn album_fans_count(&self, id: impl Into<AlbumId>) -> Result<u64> {
+        self.get_total(self.api_url(&["album", &id.into().to_string(), "fans"])).await
+    }
+
+    /// Returns the number of comments on the album with the given id, without fetching the comments.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/album/comments)
+    pub async fn album_comments_count(&self, id: impl Into<AlbumId>) -> Result<u64> {
+        self.get_total(self.api_url(&["album", &id.into().to_string(), "comments"])).await
+    }
+
     /// Returns the [`Artist`] with the given id.
     ///
     /// [Deezer Api Documentation](https://developers.deezer.com/api/artist)
-    pub async fn artist(&self, id: u64) -> Result<Option<Artist>> {
-        self.get_entity(id).await
+    pub async fn artist(&self, id: impl IntoDeezerId<ArtistId>) -> Result<Option<Artist>> {
+        self.get_entity(id.into_deezer_id()?).await
     }
 
     /// Returns the [`Album`] for Artist with the given id.
     ///
     /// [Deezer Api Documentation](https://developers.deezer.com/api/artist/albums)
-    pub async fn artist_albums(&self, id: u64, limit: Option<u32>,
+    pub async fn artist_albums(&self, id: impl Into<ArtistId>, limit: Option<u32>,
                                offset: Option<u32>) -> Result<Vec<ArtistAlbum>> {
-         self.get_subresource(id, limit, offset).await
+        let url = self.api_url(&["artist", &id.into().to_string(), "albums"]);
+
+        self.get_list_from_url(url, limit, offset).await
+    }
+
+    /// Returns a mix of tracks by the artist with the given id and similar artists.
+    ///
+    /// Each call returns a new selection of tracks.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/artist/radio)
+    pub async fn artist_radio(&self, id: impl Into<ArtistId>) -> Result<Vec<PartialTrack>> {
+        let url = self.api_url(&["artist", &id.into().to_string(), "radio"]);
+
+        self.get_list_from_url(url, None, None).await
+    }
+
+    /// Returns a mix of tracks seeded by any api object.
+    ///
+    /// The api only offers radios for artists, so tracks and albums are mixed from the radio of their artist.
+    /// Users get their personal flow, which requires an access token for the user.
+    /// The list is empty when the seed doesn't exist anymore.
+    /// Playlists can't seed a mix and fail with [`DeezerError::InvalidId`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # use deezer::models::TrackId;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let tracks = deezer.mix(TrackId::new(3135556)).await?;
+    /// # assert!(!tracks.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn mix(&self, seed: impl Into<AnyId>) -> Result<Vec<PartialTrack>> {
+        let artist = match seed.into() {
+            AnyId::Artist(id) => Some(id),
+            AnyId::Track(id) => self.track(id).await?.map(|track| track.artist.id),
+            AnyId::Album(id) => self.album(id).await?.map(|album| album.artist.id),
+            AnyId::User(id) => {
+                let url = self.api_url(&["user", &id.to_string(), "flow"]);
+                return self.get_list_from_url(url, None, None).await;
+            }
+            seed => return Err(DeezerError::InvalidId(seed.to_string())),
+        };
+
+        match artist {
+            Some(id) => self.artist_radio(id).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Fetches all genres, editorials and radios concurrently.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let catalog = deezer.bootstrap().await?;
+    /// assert!(catalog.genre(0).is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn bootstrap(&self) -> Result<Catalog> {
+        let (genres, editorials, radios) = futures::try_join!(
+            self.genres(),
+            self.editorials(),
+            self.radios(),
+        )?;
+
+        Ok(Catalog::new(genres, editorials, radios))
+    }
+
+    /// Returns all albums of the artist with the given id, grouped by release year and record type.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/artist/albums)
+    pub async fn artist_discography(&self, id: impl Into<ArtistId>) -> Result<Discography> {
+        let url = self.api_url(&["artist", &id.into().to_string(), "albums"]);
+        let albums: Vec<ArtistAlbum> = self.get_all_pages(url).await?;
+
+        Ok(Discography::new(albums))
     }
 
     /// Returns the [`Comment`] with the given id.
@@ -63,8 +545,33 @@ impl DeezerClient {
     /// Returns the [`Editorial`] with the given id.
     ///
     /// [Deezer Api Documentation](https://developers.deezer.com/api/editorial)
-    pub async fn editorial(&self, id: u64) -> Result<Option<Editorial>> {
-        self.get_entity(id).await
+    pub async fn editorial(&self, id: impl Into<GenreId>) -> Result<Option<Editorial>> {
+        self.get_entity(id.into()).await
+    }
+
+    /// Returns the new releases of the editorial with the given id which match the filter.
+    ///
+    /// The api doesn't support filtering releases, so the filter is applied to the returned releases.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # use deezer::models::{RecordType, ReleaseFilter};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let filter = ReleaseFilter::new().released_from("2024-03-04").record_type(RecordType::Album);
+    /// let albums = deezer.editorial_releases(0, &filter).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/editorial/releases)
+    pub async fn editorial_releases(&self, id: impl Into<GenreId>, filter: &ReleaseFilter) -> Result<Vec<PartialAlbum>> {
+        let url = self.api_url(&["editorial", &id.into().to_string(), "releases"]);
+        let releases: Vec<PartialAlbum> = self.get_all_pages(url).await?;
+
+        Ok(releases.into_iter().filter(|album| filter.matches(album)).collect())
     }
 
     /// Returns a List of all [`Editorial`]s.
@@ -77,8 +584,42 @@ impl DeezerClient {
     /// Returns the [`Genre`] with the given id.
     ///
     /// [Deezer Api Documentation](https://developers.deezer.com/api/genre)
-    pub async fn genre(&self, id: u64) -> Result<Option<Genre>> {
-        self.get_entity(id).await
+    pub async fn genre(&self, id: impl Into<GenreId>) -> Result<Option<Genre>> {
+        self.get_entity(id.into()).await
+    }
+
+    /// Returns the [`Radio`]s of the genre with the given id.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/genre/radios)
+    pub async fn genre_radios(&self, id: impl Into<GenreId>) -> Result<Vec<Radio>> {
+        let url = self.api_url(&["genre", &id.into().to_string(), "radios"]);
+
+        self.get_all_pages(url).await
+    }
+
+    /// Returns a batch of tracks from the first radio of the genre with the given id,
+    /// e.g. to start playing "some jazz".
+    ///
+    /// Each call returns a new selection of tracks, see [`Radio::fetch_tracks()`].
+    /// The list is empty when the genre has no radios.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// // Jazz
+    /// let tracks = deezer.radio_for_genre(129).await?;
+    /// # assert!(!tracks.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn radio_for_genre(&self, id: impl Into<GenreId>) -> Result<Vec<PartialTrack>> {
+        match self.genre_radios(id).await?.first() {
+            Some(radio) => radio.fetch_tracks(self).await,
+            None => Ok(Vec::new()),
+        }
     }
 
     /// Returns a List of all [`Genre`]s.
@@ -91,8 +632,44 @@ impl DeezerClient {
     /// Returns the [`Playlist`] with the given id.
     ///
     /// [Deezer Api Documentation](https://developers.deezer.com/api/playlist)
-    pub async fn playlist(&self, id: u64) -> Result<Option<Playlist>> {
-        self.get_entity(id).await
+    pub async fn playlist(&self, id: impl IntoDeezerId<PlaylistId>) -> Result<Option<Playlist>> {
+        self.get_entity(id.into_deezer_id()?).await
+    }
+
+    /// Returns the users which added the playlist with the given id to their favorites.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/playlist/fans)
+    pub async fn playlist_fans(&self, id: impl Into<PlaylistId>) -> Result<Vec<PartialUser>> {
+        let url = self.api_url(&["playlist", &id.into().to_string(), "fans"]);
+
+        self.get_all_pages(url).await
+    }
+
+    /// Searches for tracks, returning up to `limit` results ordered by relevance.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # use deezer::search::SearchQuery;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let query = SearchQuery::parse(r#"artist:"daft punk" track:"veridis quo""#).unwrap();
+    /// let tracks = deezer.search_tracks(&query, Some(5)).await?;
+    /// # assert!(!tracks.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/search)
+    pub async fn search_tracks(&self, query: &SearchQuery, limit: Option<u32>) -> Result<Vec<PartialTrack>> {
+        let url = self.api_url(&["search", "track"]);
+        let params = Params::new()
+            .with("q", query.to_string())
+            .with_optional("limit", limit);
+        let res: DeezerArray<PartialTrack> = self.get_with_params(url, &params).await?;
+
+        Ok(res.data)
     }
 
     /// Returns the [`Radio`] with the given id.
@@ -112,70 +689,432 @@ impl DeezerClient {
     /// Returns the [`Track`] with the given id.
     ///
     /// [Deezer Api Documentation](https://developers.deezer.com/api/track)
-    pub async fn track(&self, id: u64) -> Result<Option<Track>> {
-        self.get_entity(id).await
+    pub async fn track(&self, id: impl IntoDeezerId<TrackId>) -> Result<Option<Track>> {
+        self.get_entity(id.into_deezer_id()?).await
+    }
+
+    /// Returns the contributors of the track with the given id.
+    ///
+    /// Only the contributors are parsed from the response, which is cheaper than
+    /// hydrating the full [`Track`] e.g. for showing credits in list views.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let contributors = deezer.track_contributors(3135556).await?.unwrap();
+    /// # assert!(!contributors.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/track)
+    pub async fn track_contributors(&self, id: impl IntoDeezerId<TrackId>) -> Result<Option<Vec<ContributorArtist>>> {
+        let url = self.api_url(&["track", &id.into_deezer_id()?.to_string()]);
+        let track: Option<TrackContributors> = self.get_entity_from_url(url).await?;
+
+        Ok(track.map(|track| track.contributors))
+    }
+
+    /// Returns the [`Track`] with the given isrc.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/track)
+    pub async fn track_by_isrc(&self, isrc: Isrc) -> Result<Option<Track>> {
+        self.get_entity_by_isrc(isrc).await
     }
 
     /// Returns the [`User`] with the given id.
     ///
     /// [Deezer Api Documentation](https://developers.deezer.com/api/user)
-    pub async fn user(&self, id: u64) -> Result<Option<User>> {
-        self.get_entity(id).await
+    pub async fn user(&self, id: impl IntoDeezerId<UserId>) -> Result<Option<User>> {
+        self.get_entity(id.into_deezer_id()?).await
+    }
+
+    /// Returns the [`User`] authenticated by the access token of this client.
+    ///
+    /// Fails with [`DeezerError::InsufficientPermission`] when the client has no access token.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user)
+    pub async fn me(&self) -> Result<User> {
+        self.get(self.api_url(&["user", "me"])).await
+    }
+
+    /// Returns the entrypoint for the favorites, playlists and other resources of the authenticated user.
+    pub fn me_client(&self) -> MeClient {
+        MeClient::new(self.clone())
+    }
+
+    /// Returns all favorite tracks of the user with the given id.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/tracks)
+    pub async fn user_favorite_tracks(&self, id: impl Into<UserId>) -> Result<Vec<PartialTrack>> {
+        let url = self.api_url(&["user", &id.into().to_string(), "tracks"]);
+
+        self.get_all_pages(url).await
+    }
+
+    /// Returns all favorite albums of the user with the given id.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/albums)
+    pub async fn user_favorite_albums(&self, id: impl Into<UserId>) -> Result<Vec<PartialAlbum>> {
+        let url = self.api_url(&["user", &id.into().to_string(), "albums"]);
+
+        self.get_all_pages(url).await
+    }
+
+    /// Returns all favorite artists of the user with the given id.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/artists)
+    pub async fn user_favorite_artists(&self, id: impl Into<UserId>) -> Result<Vec<PartialArtist>> {
+        let url = self.api_url(&["user", &id.into().to_string(), "artists"]);
+
+        self.get_all_pages(url).await
+    }
+
+    /// Returns all playlists of the user with the given id.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/playlists)
+    pub async fn user_playlists(&self, id: impl Into<UserId>) -> Result<Vec<PartialPlaylist>> {
+        let url = self.api_url(&["user", &id.into().to_string(), "playlists"]);
+
+        self.get_all_pages(url).await
+    }
+
+    /// Streams all tracks of the playlist with the given id.
+    ///
+    /// The tracks are requested and parsed one page at a time, so even playlists with thousands of
+    /// tracks are never buffered completely.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/playlist/tracks)
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # use futures::TryStreamExt;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let mut tracks = Box::pin(deezer.playlist_tracks(908622995));
+    /// while let Some(track) = tracks.try_next().await? {
+    ///     println!("{}", track.title);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn playlist_tracks(&self, id: impl Into<PlaylistId>) -> impl Stream<Item = Result<PlaylistTrack>> + '_ {
+        let url = self.api_url(&["playlist", &id.into().to_string(), "tracks"]);
+
+        self.stream_pages(url)
+    }
+
+    /// Uploads a JPEG or PNG image as the picture of the playlist with the given id.
+    ///
+    /// Deezer only accepts uploads with the [`upload_token`](Infos::upload_token) of the access token,
+    /// which is requested first. Requires the [`ManageLibrary`](Permission::ManageLibrary) permission.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new().with_access_token("access-token");
+    /// let picture = std::fs::read("cover.jpg")?;
+    /// deezer.set_playlist_picture(908622995, picture).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_playlist_picture(&self, id: impl Into<PlaylistId>, image: impl Into<Bytes>) -> Result<()> {
+        self.ensure_permission(Permission::ManageLibrary).await?;
+        let image = image.into();
+        let (content_type, file_name) = if image.starts_with(b"\x89PNG") {
+            ("image/png", "picture.png")
+        } else {
+            ("image/jpeg", "picture.jpg")
+        };
+        let upload_token = self.api_info().await?.upload_token.ok_or_else(|| {
+            DeezerError::InsufficientPermission("no upload token was issued for the access token".to_string())
+        })?;
+        let file = Part::bytes(image.to_vec()).file_name(file_name).mime_str(content_type)?;
+        let url = self.upload_url.join(&format!("playlist/{}", id.into())).expect("playlist path is valid");
+        let request = self.client
+            .post(url)
+            .query(&[("upload_token", upload_token)])
+            .multipart(Form::new().part("file", file));
+        self.execute_api(request).await?;
+
+        Ok(())
+    }
+
+    /// Appends the tracks with the given ids to the playlist with the given id.
+    ///
+    /// Requires the [`ManageLibrary`](Permission::ManageLibrary) permission.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new().with_access_token("access-token");
+    /// deezer.add_playlist_tracks(908622995, vec![3135556, 3135553]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/playlist/tracks)
+    pub async fn add_playlist_tracks<T>(&self, id: impl Into<PlaylistId>, tracks: impl IntoIterator<Item = T>) -> Result<()>
+        where
+            T: Into<TrackId>,
+    {
+        self.ensure_permission(Permission::ManageLibrary).await?;
+        let songs: Vec<String> = tracks.into_iter().map(|id| id.into().to_string()).collect();
+        if songs.is_empty() {
+            return Ok(());
+        }
+        let url = self.api_url(&["playlist", &id.into().to_string(), "tracks"]);
+        let _: bool = self.post(url, &Params::new().with("songs", songs.join(","))).await?;
+
+        Ok(())
+    }
+
+    /// Reorders the tracks of the playlist with the given id, `order` contains all track ids in their new order.
+    ///
+    /// Fails with [`DeezerError::InvalidTrackOrder`] without changing the playlist
+    /// when `order` contains more or less tracks than the playlist.
+    /// Requires the [`ManageLibrary`](Permission::ManageLibrary) permission.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new().with_access_token("access-token");
+    /// deezer.reorder_playlist_tracks(908622995, vec![3135556, 3135553, 3135554]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/playlist/tracks)
+    pub async fn reorder_playlist_tracks<T>(&self, id: impl Into<PlaylistId>, order: impl IntoIterator<Item = T>) -> Result<()>
+        where
+            T: Into<TrackId>,
+    {
+        self.ensure_permission(Permission::ManageLibrary).await?;
+        let url = self.api_url(&["playlist", &id.into().to_string(), "tracks"]);
+        let order: Vec<String> = order.into_iter().map(|id| id.into().to_string()).collect();
+        let expected = self.get_total(url.clone()).await?;
+        if expected != order.len() as u64 {
+            return Err(DeezerError::InvalidTrackOrder {
+                expected,
+                actual: order.len() as u64,
+            });
+        }
+        let _: bool = self.post(url, &Params::new().with("order", order.join(","))).await?;
+
+        Ok(())
+    }
+
+    /// Fetches the given objects into the cache, keeping at most 8 requests in flight.
+    ///
+    /// Later lookups of the objects are served from the cache, e.g. to prepare the next screen of an application.
+    /// Does nothing when the client has no cache, see [`with_cache()`](DeezerClient::with_cache).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # use deezer::models::{AlbumId, AnyId, ArtistId};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new().with_cache(100);
+    /// deezer.warm_cache(vec![AnyId::from(AlbumId::new(302127)), AnyId::from(ArtistId::new(27))]).await?;
+    ///
+    /// // served from the cache
+    /// let album = deezer.album(302127).await?;
+    /// # assert!(album.is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn warm_cache(&self, ids: impl IntoIterator<Item = AnyId>) -> Result<()> {
+        if self.cache.is_none() {
+            return Ok(());
+        }
+        fetch::batched(ids, WARM_UP_CONCURRENCY, |id| async move {
+            match id {
+                AnyId::Album(id) => self.album(id).await.map(drop),
+                AnyId::Artist(id) => self.artist(id).await.map(drop),
+                AnyId::Playlist(id) => self.playlist(id).await.map(drop),
+                AnyId::Track(id) => self.track(id).await.map(drop),
+                AnyId::User(id) => self.user(id).await.map(drop),
+            }
+        })
+        .await?;
+
+        Ok(())
     }
 
     /// Returns the information about the API in the current country
     ///
     /// [Deezer Api Documentation](https://developers.deezer.com/api/infos)
     pub async fn api_info(&self) -> Result<Infos> {
-        let url = format!("{}/infos", BASE_URL);
-        self.get(&url).await
+        self.get(self.api_url(&["infos"])).await
     }
 
-    /// Returns charts of a specified genre
+    /// Returns the country the api is accessed from, as determined by deezer.
+    ///
+    /// Returns the country set with [`with_country()`](DeezerClient::with_country) without requesting the api.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// if let Some(country) = deezer.current_country().await? {
+    ///     println!("accessing deezer from {}", country);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn current_country(&self) -> Result<Option<CountryCode>> {
+        if let Some(country) = self.country {
+            return Ok(Some(country));
+        }
+        let infos = self.api_info().await?;
+
+        Ok(infos.country_code())
+    }
+
+    /// Returns the charts of all genres
     ///
     /// [Deezer Api Documentation](https://developers.deezer.com/api/chart)
     pub async fn charts(&self) -> Result<Chart> {
-        let url = format!("{}/chart", BASE_URL);
-        self.get(&url).await
+        self.chart_for_genre(GenreId::ALL).await
+    }
+
+    /// Returns the charts of the genre with the given id
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/chart)
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// // Pop
+    /// let chart = deezer.chart_for_genre(132).await?;
+    /// # assert!(!chart.tracks.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn chart_for_genre(&self, id: impl Into<GenreId>) -> Result<Chart> {
+        self.get(self.api_url(&["chart", &id.into().to_string()])).await
     }
 
     /// Returns the user's options
     ///
     /// [Deezer Api Documentation](https://developers.deezer.com/api/options)
     pub async fn user_options(&self) -> Result<Options> {
-        let url = format!("{}/options", BASE_URL);
-        self.get(&url).await
+        self.get(self.api_url(&["options"])).await
+    }
+
+    /// Builds an absolute api url from its path segments, reusing the parsed base url.
+    pub(crate) fn api_url<I>(&self, segments: I) -> Url
+        where
+            I: IntoIterator,
+            I::Item: AsRef<str>,
+    {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .expect("base url has a path")
+            .pop_if_empty()
+            .extend(segments);
+
+        url
     }
 
-    pub(crate) async fn get_entity_from_url<T>(&self, url:String) -> Result<Option<T>>
+    pub(crate) async fn get_entity_from_url<T>(&self, url: Url) -> Result<Option<T>>
         where
             T: DeserializeOwned,
     {
-        let res = self.client.get(&url).send().await?;
-        if res.status() == StatusCode::NOT_FOUND {
-            return Ok(None);
+        if let Some(body) = self.cache.as_ref().and_then(|cache| cache.get(&url)) {
+            return Ok(Some(serde_json::from_slice(&body)?));
+        }
+        let body = match self.execute_api(self.client.get(url.clone())).await {
+            Err(DeezerError::HttpError(err)) if err.status() == Some(StatusCode::NOT_FOUND) => return Ok(None),
+            result => result?,
+        };
+        // deezer answers unknown ids, isrcs and upcs with an error object instead of a 404
+        match ApiError::from_body(&body) {
+            Some(error) if error.code == DATA_NOT_FOUND => return Ok(None),
+            Some(error) => return Err(error.into()),
+            None => {}
+        }
+        let entity = serde_json::from_slice(&body)?;
+        if let Some(cache) = &self.cache {
+            cache.insert(url, body);
         }
-        let body = res.error_for_status()?.json().await?;
 
-        Ok(Some(body))
+        Ok(Some(entity))
     }
 
-    pub(crate) async fn get_entity<T>(&self, id: u64) -> Result<Option<T>>
+    pub(crate) async fn get_entity<T>(&self, id: T::Id) -> Result<Option<T>>
     where
         T: DeezerObject,
     {
-        let url = T::get_api_url(id);
-        let url = format!("{}/{}", BASE_URL, url);
+        let url = self.api_url(T::get_api_url(id).split('/'));
 
         self.get_entity_from_url(url).await
     }
 
+    pub(crate) async fn entity_exists<T>(&self, id: T::Id) -> Result<bool>
+        where
+            T: DeezerObject,
+    {
+        let url = self.api_url(T::get_api_url(id).split('/'));
+        let body = match self.execute_api(self.client.get(url)).await {
+            Err(DeezerError::HttpError(err)) if err.status() == Some(StatusCode::NOT_FOUND) => return Ok(false),
+            result => result?,
+        };
+        // deezer answers unknown ids with an error object instead of a 404
+        match ApiError::from_body(&body) {
+            Some(error) if error.code == DATA_NOT_FOUND => Ok(false),
+            Some(error) => Err(error.into()),
+            None => Ok(true),
+        }
+    }
+
+    /// Fetches all entities with the given ids, keeping at most `concurrency` requests in flight.
+    ///
+    /// Entities which could not be found fail with [`DeezerError::NotFound`], without affecting the others.
+    pub(crate) async fn get_entities<T>(&self, ids: impl IntoIterator<Item = T::Id>,
+                                        concurrency: usize) -> BatchResult<T, T::Id>
+        where
+            T: DeezerObject,
+    {
+        fetch::settled(ids, concurrency, |id| async move {
+            self.get_entity(id)
+                .await?
+                .ok_or_else(|| DeezerError::NotFound(T::get_api_url(id)))
+        })
+        .await
+    }
+
     pub(crate) async fn get_entity_by_upc<T>(&self, upc: Upc) -> Result<Option<T>>
         where
             T: DeezerUpcObject,
     {
-        let url = T::get_api_url(upc);
-        let url = format!("{}/{}", BASE_URL, url);
+        let url = self.api_url(T::get_api_url(upc).split('/'));
+
+        self.get_entity_from_url(url).await
+    }
+
+    pub(crate) async fn get_entity_by_isrc<T>(&self, isrc: Isrc) -> Result<Option<T>>
+        where
+            T: DeezerIsrcObject,
+    {
+        let url = self.api_url(T::get_api_url(isrc).split('/'));
 
         self.get_entity_from_url(url).await
     }
@@ -184,59 +1123,345 @@ impl DeezerClient {
     where
         T: DeezerEnumerable,
     {
-        let url = T::get_all_api_url();
-        let url = format!("{}/{}", BASE_URL, url);
+        let url = self.api_url(T::get_all_api_url().split('/'));
 
-        let res: DeezerArray<T> = self.get(&url).await?;
+        let res: DeezerArray<T> = self.get(url).await?;
 
         Ok(res.data)
     }
 
-    pub(crate) async fn get_subresource<T>(&self, id: u64, limit: Option<u32>,
-                                           offset: Option<u32>) -> Result<Vec<T>>
+    /// Fetches a single page of a list from an absolute api url, like the `tracklist` links of the models.
+    pub(crate) async fn get_list_from_url<T>(&self, url: impl IntoUrl, limit: Option<u32>,
+                                             offset: Option<u32>) -> Result<Vec<T>>
         where
-            T: DeezerObject
+            T: DeserializeOwned
     {
-        let url = T::get_api_url(id);
-        let url = format!("{}/{}", BASE_URL, url);
+        let params = Params::new()
+            .with_optional("limit", limit)
+            .with_optional("offset", offset);
+
+        let res: DeezerArray<T> = self.get_with_params(url, &params).await?;
 
-        let mut params: HashMap<String, String> = HashMap::new();
-        if let Some(limit) = limit {
-            params.insert("limit".to_owned(), limit.to_string());
+        Ok(res.data)
+    }
+
+    /// Returns the total number of items of a paginated list, requesting only a single item.
+    ///
+    /// Lists without pagination metadata are counted by fetching all their items.
+    async fn get_total(&self, url: Url) -> Result<u64> {
+        let params = Params::new().with("limit", 1);
+        let page: DeezerArray<IgnoredAny> = self.get_with_params(url.clone(), &params).await?;
+        if let Some(total) = page.total {
+            return Ok(total);
         }
-        if let Some(offset) = offset {
-            params.insert("offset".to_owned(), offset.to_string());
+        let items: Vec<IgnoredAny> = self.get_all_pages(url).await?;
+
+        Ok(items.len() as u64)
+    }
+
+    /// Returns the checksum deezer sends along with a list, requesting only a single item.
+    ///
+    /// The checksum covers the whole list, `None` when the list has none.
+    pub(crate) async fn get_checksum(&self, url: Url) -> Result<Option<String>> {
+        let params = Params::new().with("limit", 1);
+        let list: ListChecksum = self.get_with_params(url, &params).await?;
+
+        Ok(list.checksum)
+    }
+
+    /// Fetches all items of a paginated list, following the `next` urls until the last page.
+    pub(crate) async fn get_all_pages<T>(&self, url: impl IntoUrl) -> Result<Vec<T>>
+        where
+            T: DeserializeOwned
+    {
+        let mut page: DeezerArray<T> = self.get(url).await?;
+        let mut items = std::mem::take(&mut page.data);
+        while let Some(next) = page.next {
+            page = self.get(next).await?;
+            items.append(&mut page.data);
         }
 
-        let res: DeezerArray<T> = self.get_with_params(&url, &params).await?;
+        Ok(items)
+    }
 
-        Ok(res.data)
+    /// Streams the items of a paginated list, requesting the next page once the previous one is consumed.
+    pub(crate) fn stream_pages<T>(&self, url: impl IntoUrl) -> impl Stream<Item = Result<T>> + '_
+        where
+            T: DeserializeOwned
+    {
+        let first = url.into_url().map(String::from);
+        stream::try_unfold(Some(first), move |next| async move {
+            let url = match next {
+                Some(url) => url?,
+                None => return Ok::<_, DeezerError>(None),
+            };
+            let page: DeezerArray<T> = self.get(url).await?;
+
+            Ok(Some((page.data, page.next.map(Ok))))
+        })
+        .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+        .try_flatten()
+    }
+
+    /// Downloads the body of the given url, e.g. an image.
+    ///
+    /// The download is subject to the rate limit and recorded like api requests, but never carries the access token.
+    pub(crate) async fn get_bytes(&self, url: &str) -> Result<Bytes> {
+        let res = self.send_with_token(self.client.get(url), 0, None).await?.error_for_status()?;
+
+        Ok(res.bytes().await?)
     }
 
-    async fn get_with_optional_params<T: DeserializeOwned>(&self, url: &str, query_params: Option<&HashMap<String, String>>) -> Result<T> {
+    /// Streams the body of the given url, e.g. to download media files.
+    ///
+    /// Like [`get_bytes()`](DeezerClient::get_bytes) the access token isn't sent.
+    pub(crate) async fn get_bytes_stream(&self, url: &str) -> Result<impl Stream<Item = Result<Bytes>>> {
+        let res = self.send_with_token(self.client.get(url), 0, None).await?.error_for_status()?;
+
+        Ok(res.bytes_stream().map_err(DeezerError::from))
+    }
+
+    async fn get_with_optional_params<T: DeserializeOwned>(&self, url: impl IntoUrl, query_params: Option<&Params>) -> Result<T> {
         let mut request_builder = self
             .client
             .get(url);
         if let Some(params) = query_params {
             request_builder = request_builder.query(params);
         }
-        let res =
-            request_builder
-                .send()
-                .await?
-                .error_for_status()?
-                .json()
-                .await?;
+        let body = self.execute_api(request_builder).await?;
+
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Sends an api request and returns the response body.
+    ///
+    /// When the access token is rejected and a reauthentication callback is registered
+    /// the request is sent once more with a new token.
+    pub(crate) async fn execute_api(&self, request: reqwest::RequestBuilder) -> Result<Bytes> {
+        let reauthentication = match &self.reauthentication {
+            Some(reauthentication) => reauthentication,
+            None => return self.execute_checked(request, 0).await,
+        };
+        if self.token_store.get().await?.is_some_and(|token| token.is_expired()) {
+            log::info!("The access token expired, reauthenticating");
+            self.reauthenticate(reauthentication).await?;
+        }
+        let replay = request.try_clone();
+        match self.execute_checked(request, 0).await {
+            Err(err) if err.is_token_error() => match replay {
+                Some(replay) => {
+                    log::info!("The access token was rejected ({}), reauthenticating", err);
+                    self.reauthenticate(reauthentication).await?;
+
+                    self.execute_checked(replay, 1).await
+                }
+                None => Err(err),
+            },
+            result => result,
+        }
+    }
+
+    async fn reauthenticate(&self, reauthentication: &Reauthentication) -> Result<()> {
+        let mut token = (reauthentication.0)().await?;
+        if token.permissions.is_none() {
+            // refreshing doesn't change the permissions the user granted to the application
+            token.permissions = self.token_store.get().await?.and_then(|previous| previous.permissions);
+        }
+
+        self.token_store.set(token).await
+    }
+
+    /// Sends an api request, turning error bodies caused by the access token into errors.
+    async fn execute_checked(&self, request: reqwest::RequestBuilder, resend_count: u32) -> Result<Bytes> {
+        let body = self.send(request, resend_count).await?.error_for_status()?.bytes().await?;
+        if let Some(error) = ApiError::from_body(&body) {
+            if let Some(err) = error.into_token_error() {
+                return Err(err);
+            }
+        }
+
+        Ok(body)
+    }
+
+    /// Sends an api request after waiting for the rate limit, `resend_count` is the number of previous attempts to send it.
+    async fn send(&self, request: reqwest::RequestBuilder, resend_count: u32) -> Result<reqwest::Response> {
+        let token = self.token_store.get().await?.map(|StoredToken { token, .. }| token);
+
+        self.send_with_token(request, resend_count, token.as_deref()).await
+    }
+
+    /// Sends a request after waiting for the rate limit, authenticated with the given access token.
+    async fn send_with_token(&self, request: reqwest::RequestBuilder, resend_count: u32,
+                             token: Option<&str>) -> Result<reqwest::Response> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+        let request = match &self.correlation_id {
+            Some(correlation_id) => {
+                let value = (correlation_id.generate)();
+                log::debug!("Sending api request with {}: {:?}", correlation_id.header, value);
+                request.header(correlation_id.header.clone(), value)
+            }
+            None => request,
+        };
+        let mut request = request.build()?;
+        if let Some(token) = token {
+            // a token set on the request itself, e.g. via `RequestBuilder::access_token`, takes precedence
+            if !request.url().query_pairs().any(|(key, _)| key == "access_token") {
+                request.url_mut().query_pairs_mut().append_pair("access_token", token);
+            }
+        }
+        let observed = (self.request_log.is_some() || self.journal.is_some())
+            .then(|| (request.method().clone(), request.url().clone(), SystemTime::now(), Instant::now()));
+
+        #[cfg(feature = "otel")]
+        let response = crate::otel::send_instrumented(&self.client, request, resend_count).await;
+        #[cfg(not(feature = "otel"))]
+        let response = self.client.execute(request).await;
+
+        if let Some((method, url, timestamp, started)) = observed {
+            let elapsed = started.elapsed();
+            if let Some(log) = &self.request_log {
+                log.log(&method, &url, resend_count, &response, elapsed);
+            }
+            if let Some(journal) = &self.journal {
+                let mut endpoint = url.clone();
+                endpoint.set_query(None);
+                journal.record(&JournalEntry {
+                    timestamp,
+                    method: method.to_string(),
+                    endpoint: endpoint.to_string(),
+                    params: logging::public_params(&url),
+                    duration: elapsed,
+                    status: response.as_ref().ok().map(|response| response.status().as_u16()),
+                    error: response.as_ref().err().map(logging::describe),
+                });
+            }
+        }
+
+        Ok(response?)
+    }
+
+    /// Sends a write request to the api, parsing the response body.
+    pub(crate) async fn post<T: DeserializeOwned>(&self, url: Url, params: &Params) -> Result<T> {
+        let body = self.execute_api(self.client.post(url).query(params)).await?;
+        // writes are rejected with an error object, e.g. when adding a track twice
+        if let Some(error) = ApiError::from_body(&body) {
+            return Err(error.into());
+        }
+
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Sends a delete request to the api, parsing the response body.
+    pub(crate) async fn delete<T: DeserializeOwned>(&self, url: Url, params: &Params) -> Result<T> {
+        let body = self.execute_api(self.client.delete(url).query(params)).await?;
+        if let Some(error) = ApiError::from_body(&body) {
+            return Err(error.into());
+        }
 
-        Ok(res)
+        Ok(serde_json::from_slice(&body)?)
     }
 
-    async fn get_with_params<T: DeserializeOwned>(&self, url: &str, query_params: &HashMap<String, String>) -> Result<T> {
+    async fn get_with_params<T: DeserializeOwned>(&self, url: impl IntoUrl, query_params: &Params) -> Result<T> {
         self.get_with_optional_params(url, Some(query_params)).await
     }
 
-    async fn get<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
+    async fn get<T: DeserializeOwned>(&self, url: impl IntoUrl) -> Result<T> {
         self.get_with_optional_params(url, None).await
     }
 
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::MockServer;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\npicture";
+
+    async fn client(server: &MockServer) -> DeezerClient {
+        DeezerClient::new()
+            .with_access_token("access-token")
+            .with_base_url(server.url())
+            .with_upload_url(server.url().join("upload/").unwrap())
+    }
+
+    #[tokio::test]
+    async fn playlist_pictures_are_uploaded_with_the_upload_token() {
+        let server = MockServer::start().await;
+        server
+            .respond("/infos", r#"{"country_iso": "DE", "country": "Germany", "open": true, "offers": [], "upload_token": "upload-token", "upload_token_lifetime": 3600}"#)
+            .respond("/upload/playlist/908622995", "true");
+
+        client(&server).await.set_playlist_picture(908622995, PNG).await.unwrap();
+
+        let uploads = server.requests_to("/upload/playlist/908622995");
+        assert_eq!(uploads.len(), 1);
+        let upload = &uploads[0];
+        assert_eq!(upload.method, "POST");
+        assert_eq!(upload.query("upload_token").as_deref(), Some("upload-token"));
+        assert_eq!(upload.query("access_token").as_deref(), Some("access-token"));
+        assert!(upload.headers["content-type"].starts_with("multipart/form-data; boundary="));
+        let body = String::from_utf8_lossy(&upload.body);
+        assert!(body.contains(r#"name="file"; filename="picture.png""#));
+        assert!(body.contains("Content-Type: image/png"));
+        assert!(upload.body.windows(PNG.len()).any(|window| window == PNG));
+    }
+
+    #[tokio::test]
+    async fn reauthentication_keeps_the_permissions() {
+        let server = MockServer::start().await;
+        server
+            .respond("/user/me", r#"{"error": {"type": "OAuthException", "message": "Invalid OAuth access token.", "code": 300}}"#)
+            .respond("/user/me", serde_json::to_string(&User::new(2529, "dadbond")).unwrap());
+        let token = StoredToken::new("old-token").with_permissions(vec![Permission::BasicAccess, Permission::ManageLibrary]);
+        let client = DeezerClient::new()
+            .with_base_url(server.url())
+            .with_token_store(MemoryTokenStore::new(token))
+            .with_reauthentication(|| async { Ok("new-token".to_string()) });
+
+        client.me().await.unwrap();
+
+        let stored = client.token_store.get().await.unwrap().unwrap();
+        assert_eq!(stored.token, "new-token");
+        assert_eq!(stored.permissions, Some(vec![Permission::BasicAccess, Permission::ManageLibrary]));
+        assert!(matches!(client.ensure_permission(Permission::DeleteLibrary).await, Err(DeezerError::MissingPermission(_))));
+        let tokens: Vec<_> = server.requests_to("/user/me").iter().map(|request| request.query("access_token")).collect();
+        assert_eq!(tokens, vec![Some("old-token".to_string()), Some("new-token".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn reauthentication_records_the_expiry_of_oauth_tokens() {
+        let server = MockServer::start().await;
+        server
+            .respond("/user/me", r#"{"error": {"type": "OAuthException", "message": "Invalid OAuth access token.", "code": 300}}"#)
+            .respond("/user/me", serde_json::to_string(&User::new(2529, "dadbond")).unwrap());
+        let client = DeezerClient::new()
+            .with_base_url(server.url())
+            .with_access_token("old-token")
+            .with_reauthentication(|| async {
+                Ok(AccessToken {
+                    token: "new-token".to_string(),
+                    expires_in: Some(std::time::Duration::from_secs(3600)),
+                })
+            });
+
+        client.me().await.unwrap();
+
+        let stored = client.token_store.get().await.unwrap().unwrap();
+        assert_eq!(stored.token, "new-token");
+        assert!(stored.expires_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn playlist_pictures_require_an_upload_token() {
+        let server = MockServer::start().await;
+        server.respond("/infos", r#"{"country_iso": "DE", "country": "Germany", "open": true, "offers": []}"#);
+
+        let result = client(&server).await.set_playlist_picture(908622995, PNG).await;
+
+        assert!(matches!(result, Err(DeezerError::InsufficientPermission(_))));
+        assert!(server.requests_to("/upload/playlist/908622995").is_empty());
+    }
+}
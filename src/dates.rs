@@ -0,0 +1,15 @@
+use std::convert::TryFrom;
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Parses a `YYYY-MM-DD` date as returned by the api.
+///
+/// Deezer uses `0000-00-00` or an empty string for unknown dates, those are returned as `None`.
+pub(crate) fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+/// Converts a unix timestamp in seconds as returned by the api.
+pub(crate) fn from_timestamp(timestamp: u64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(i64::try_from(timestamp).ok()?, 0)
+}
@@ -1,9 +1,136 @@
 #![warn(missing_docs)]
 use thiserror::Error;
 
+use crate::auth::Permission;
+
+/// Code of the error object deezer answers with once the request quota is exceeded
+const QUOTA_EXCEEDED: u32 = 4;
+
+/// Code of the error object deezer answers with while it's overloaded
+const SERVICE_BUSY: u32 = 700;
+
 /// Every api which can fail will return a [`DeezerError`].
 #[derive(Debug, Error)]
 pub enum DeezerError {
+    /// The http request failed or the response could not be parsed
     #[error(transparent)]
     HttpError(#[from] reqwest::Error),
+
+    /// Reading or writing local data failed
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+
+    /// Local data could not be serialized or deserialized
+    #[error(transparent)]
+    JsonError(#[from] serde_json::Error),
+
+    /// The given id or link doesn't identify an object of the requested type
+    #[error("invalid id: {0}")]
+    InvalidId(String),
+
+    /// The api answered with an error object, e.g. because the request quota was exceeded
+    #[error("api error {code}: {message}")]
+    ApiError {
+        /// The error code, see the [Deezer Api Documentation](https://developers.deezer.com/api/errors)
+        code: u32,
+        /// The message describing the error
+        message: String,
+    },
+
+    /// The requested object doesn't exist, e.g. an item of a batch
+    #[error("not found: {0}")]
+    NotFound(String),
+
+    /// The new order of a playlist doesn't contain the same number of tracks as the playlist
+    #[error("invalid track order: the playlist has {expected} tracks, the order contains {actual}")]
+    InvalidTrackOrder {
+        /// The number of tracks of the playlist
+        expected: u64,
+        /// The number of tracks in the order
+        actual: u64,
+    },
+
+    /// The operation was cancelled with a [`CancellationToken`](crate::cancel::CancellationToken)
+    #[error("operation cancelled")]
+    Cancelled,
+
+    /// The authorization code couldn't be exchanged for an access token
+    #[error("authorization failed: {0}")]
+    AuthorizationFailed(String),
+
+    /// The api rejected the access token
+    #[error("invalid access token: {0}")]
+    InvalidToken(String),
+
+    /// The access token has expired
+    #[error("expired access token: {0}")]
+    TokenExpired(String),
+
+    /// The access token lacks a permission required by the request
+    #[error("insufficient permission: {0}")]
+    InsufficientPermission(String),
+
+    /// The request was not sent as the access token hasn't been granted the permission
+    #[error("missing permission: {0}")]
+    MissingPermission(Permission),
+}
+
+impl DeezerError {
+    /// Returns `true` when the access token has to be replaced, because it's invalid or expired.
+    pub fn is_token_error(&self) -> bool {
+        matches!(self, DeezerError::InvalidToken(_) | DeezerError::TokenExpired(_))
+    }
+
+    /// Returns whether the request may succeed when sent again,
+    /// e.g. after a timeout, a server error or once the request quota has been reset.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            DeezerError::HttpError(err) => {
+                err.is_timeout()
+                    || err.is_connect()
+                    || err.status().is_some_and(|status| status.is_server_error() || status.as_u16() == 429)
+            }
+            DeezerError::ApiError { code, .. } => *code == QUOTA_EXCEEDED || *code == SERVICE_BUSY,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` when the request was rejected because the request quota was exceeded.
+    ///
+    /// Deezer usually reports this with an error object in a successful response, rarely with status 429.
+    pub fn is_rate_limited(&self) -> bool {
+        match self {
+            DeezerError::HttpError(err) => err.status().is_some_and(|status| status.as_u16() == 429),
+            DeezerError::ApiError { code, .. } => *code == QUOTA_EXCEEDED,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_error(code: u32) -> DeezerError {
+        DeezerError::ApiError { code, message: String::new() }
+    }
+
+    #[test]
+    fn quota_and_overload_errors_are_transient() {
+        assert!(api_error(QUOTA_EXCEEDED).is_transient());
+        assert!(api_error(SERVICE_BUSY).is_transient());
+    }
+
+    #[test]
+    fn other_api_errors_are_permanent() {
+        assert!(!api_error(200).is_transient());
+        assert!(!api_error(800).is_transient());
+        assert!(!DeezerError::NotFound("album 1".to_string()).is_transient());
+    }
+
+    #[test]
+    fn only_quota_errors_are_rate_limited() {
+        assert!(api_error(QUOTA_EXCEEDED).is_rate_limited());
+        assert!(!api_error(SERVICE_BUSY).is_rate_limited());
+    }
 }
@@ -0,0 +1,257 @@
+//! Export of playlists and track lists into XSPF, M3U and CSV.
+//!
+//! All exporters take [`ExportTrack`]s, which can be created from both [`Track`]s and
+//! [`PartialTrack`]s. Exporting hydrated tracks includes fields partial tracks lack, like the ISRC.
+#![warn(missing_docs)]
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::models::{ImageSize, PartialTrack, Track};
+
+#[cfg(feature = "jsonl")]
+pub mod jsonl;
+
+/// The fields of a track used by the exporters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ExportTrack<'a> {
+    /// Title of the track
+    pub title: &'a str,
+
+    /// Name of the track's artist
+    pub artist: &'a str,
+
+    /// Title of the track's album
+    pub album: Option<&'a str>,
+
+    /// International Standard Recording Code of the track
+    pub isrc: Option<&'a str>,
+
+    /// Duration of the track
+    pub duration: Duration,
+
+    /// Url of the track on Deezer
+    pub link: Option<&'a str>,
+
+    /// Url of the track's cover
+    pub cover: Option<&'a str>,
+}
+
+impl<'a> From<&'a Track> for ExportTrack<'a> {
+    fn from(track: &'a Track) -> Self {
+        ExportTrack {
+            title: &track.title,
+            artist: &track.artist.name,
+            album: Some(&track.album.title),
+            isrc: Some(&track.isrc).filter(|isrc| !isrc.is_empty()).map(String::as_str),
+            duration: track.duration(),
+            link: Some(&track.link),
+            cover: track.album.image_url(ImageSize::Big),
+        }
+    }
+}
+
+impl<'a> From<&'a PartialTrack> for ExportTrack<'a> {
+    fn from(track: &'a PartialTrack) -> Self {
+        ExportTrack {
+            title: &track.title,
+            artist: &track.artist.name,
+            album: track.album.as_ref().map(|album| album.title.as_str()),
+            isrc: None,
+            duration: track.duration(),
+            link: track.link.as_deref(),
+            cover: track.album.as_ref().and_then(|album| album.image_url(ImageSize::Big)),
+        }
+    }
+}
+
+/// Renders the tracks as an [XSPF](https://xspf.org/spec) document.
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::models::*;
+/// # use deezer::export::{self, ExportTrack};
+/// let mut track = PartialTrack::new(3135556, "Harder, Better, Faster, Stronger");
+/// track.artist = PartialArtist::new(27, "Daft Punk");
+/// track.duration_in_seconds = 224;
+///
+/// let xspf = export::to_xspf(Some("Daft & Friends"), [ExportTrack::from(&track)]);
+/// assert!(xspf.contains("<title>Daft &amp; Friends</title>"));
+/// assert!(xspf.contains("<creator>Daft Punk</creator>"));
+/// assert!(xspf.contains("<duration>224000</duration>"));
+/// ```
+pub fn to_xspf<'a>(title: Option<&str>, tracks: impl IntoIterator<Item = ExportTrack<'a>>) -> String {
+    let mut xspf = String::new();
+    xspf.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xspf.push_str("<playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\">\n");
+    if let Some(title) = title {
+        xml_element(&mut xspf, 1, "title", title);
+    }
+    xspf.push_str("  <trackList>\n");
+    for track in tracks {
+        xspf.push_str("    <track>\n");
+        if let Some(link) = track.link {
+            xml_element(&mut xspf, 3, "location", link);
+        }
+        if let Some(isrc) = track.isrc {
+            xml_element(&mut xspf, 3, "identifier", &format!("isrc:{}", isrc));
+        }
+        xml_element(&mut xspf, 3, "title", track.title);
+        xml_element(&mut xspf, 3, "creator", track.artist);
+        if let Some(cover) = track.cover {
+            xml_element(&mut xspf, 3, "image", cover);
+        }
+        if let Some(album) = track.album {
+            xml_element(&mut xspf, 3, "album", album);
+        }
+        xml_element(&mut xspf, 3, "duration", &track.duration.as_millis().to_string());
+        xspf.push_str("    </track>\n");
+    }
+    xspf.push_str("  </trackList>\n");
+    xspf.push_str("</playlist>\n");
+
+    xspf
+}
+
+/// Renders the tracks as an extended M3U playlist.
+///
+/// The entries point to the tracks' Deezer links, tracks without a link are left out.
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::models::*;
+/// # use deezer::export::{self, ExportTrack};
+/// let mut track = PartialTrack::new(3135556, "Harder, Better, Faster, Stronger");
+/// track.artist = PartialArtist::new(27, "Daft Punk");
+/// track.duration_in_seconds = 224;
+/// track.link = Some("https://www.deezer.com/track/3135556".to_string());
+///
+/// assert_eq!(
+///     export::to_m3u([ExportTrack::from(&track)]),
+///     "#EXTM3U\n#EXTINF:224,Daft Punk - Harder, Better, Faster, Stronger\nhttps://www.deezer.com/track/3135556\n"
+/// );
+/// ```
+pub fn to_m3u<'a>(tracks: impl IntoIterator<Item = ExportTrack<'a>>) -> String {
+    let mut m3u = String::from("#EXTM3U\n");
+    for track in tracks {
+        if let Some(link) = track.link {
+            let _ = writeln!(m3u, "#EXTINF:{},{} - {}", track.duration.as_secs(), track.artist, track.title);
+            let _ = writeln!(m3u, "{}", link);
+        }
+    }
+
+    m3u
+}
+
+/// Renders the tracks as CSV with the columns `title,artist,album,isrc,duration,link`.
+///
+/// The duration is given in seconds, missing values are left empty.
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::models::*;
+/// # use deezer::export::{self, ExportTrack};
+/// let mut track = PartialTrack::new(3135556, "Harder, Better, Faster, Stronger");
+/// track.artist = PartialArtist::new(27, "Daft Punk");
+/// track.duration_in_seconds = 224;
+///
+/// assert_eq!(
+///     export::to_csv([ExportTrack::from(&track)]),
+///     "title,artist,album,isrc,duration,link\n\"Harder, Better, Faster, Stronger\",Daft Punk,,,224,\n"
+/// );
+/// ```
+pub fn to_csv<'a>(tracks: impl IntoIterator<Item = ExportTrack<'a>>) -> String {
+    let mut csv = String::from("title,artist,album,isrc,duration,link\n");
+    for track in tracks {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{}",
+            escape_csv(track.title),
+            escape_csv(track.artist),
+            escape_csv(track.album.unwrap_or_default()),
+            escape_csv(track.isrc.unwrap_or_default()),
+            track.duration.as_secs(),
+            escape_csv(track.link.unwrap_or_default())
+        );
+    }
+
+    csv
+}
+
+/// Quotes a CSV field when it contains a separator, quote or line break.
+fn escape_csv(field: &str) -> String {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn xml_element(xml: &mut String, depth: usize, name: &str, content: &str) {
+    let _ = writeln!(xml, "{:indent$}<{name}>{}</{name}>", "", escape_xml(content), indent = depth * 2, name = name);
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PartialArtist;
+
+    #[test]
+    fn csv_fields_are_quoted_when_needed() {
+        let cases = [
+            ("Around the World", "Around the World"),
+            ("", ""),
+            ("Harder, Better", "\"Harder, Better\""),
+            ("12\" Mix", "\"12\"\" Mix\""),
+            ("two\nlines", "\"two\nlines\""),
+            ("carriage\rreturn", "\"carriage\rreturn\""),
+            ("\"", "\"\"\"\""),
+        ];
+        for (field, expected) in cases {
+            assert_eq!(escape_csv(field), expected, "{:?}", field);
+        }
+    }
+
+    #[test]
+    fn xml_special_characters_are_escaped() {
+        assert_eq!(escape_xml("<Rock & Roll> \"Mix\" 'Edit'"), "&lt;Rock &amp; Roll&gt; &quot;Mix&quot; &apos;Edit&apos;");
+        assert_eq!(escape_xml("Daft Punk"), "Daft Punk");
+    }
+
+    #[test]
+    fn m3u_leaves_out_tracks_without_link() {
+        let mut track = PartialTrack::new(3135556, "Harder, Better, Faster, Stronger");
+        track.artist = PartialArtist::new(27, "Daft Punk");
+
+        assert_eq!(to_m3u([ExportTrack::from(&track)]), "#EXTM3U\n");
+    }
+
+    #[test]
+    fn xspf_escapes_track_fields() {
+        let mut track = PartialTrack::new(3135556, "Rock & Roll");
+        track.artist = PartialArtist::new(27, "<Daft Punk>");
+
+        let xspf = to_xspf(None, [ExportTrack::from(&track)]);
+        assert!(xspf.contains("      <title>Rock &amp; Roll</title>\n"));
+        assert!(xspf.contains("      <creator>&lt;Daft Punk&gt;</creator>\n"));
+        assert!(!xspf.contains("<location>"));
+    }
+}
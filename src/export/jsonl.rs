@@ -0,0 +1,81 @@
+//! Export of api objects as [JSON Lines](https://jsonlines.org/).
+//!
+//! Each object is written as one line of JSON, using the field names of the api.
+#![warn(missing_docs)]
+use std::io::Write;
+
+use serde::Serialize;
+
+use crate::Result;
+
+/// Writes api objects to `W`, one JSON document per line.
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::models::*;
+/// # use deezer::export::jsonl::JsonlWriter;
+/// # fn main() -> Result<(), deezer::DeezerError> {
+/// let mut writer = JsonlWriter::new(Vec::new());
+/// writer.write(&PartialArtist::new(27, "Daft Punk"))?;
+/// writer.write(&PartialArtist::new(1424821, "Lana Del Rey"))?;
+///
+/// let output = String::from_utf8(writer.into_inner()?).unwrap();
+/// assert_eq!(output, "{\"id\":27,\"name\":\"Daft Punk\"}\n{\"id\":1424821,\"name\":\"Lana Del Rey\"}\n");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct JsonlWriter<W: Write> {
+    writer: W,
+    written: usize,
+}
+
+impl<W: Write> JsonlWriter<W> {
+    /// Creates a writer writing to `writer`.
+    ///
+    /// Writes are not buffered, wrap files in a [`BufWriter`](std::io::BufWriter).
+    pub fn new(writer: W) -> Self {
+        JsonlWriter { writer, written: 0 }
+    }
+
+    /// Writes a single object as one line.
+    pub fn write<T: Serialize>(&mut self, item: &T) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, item)?;
+        self.writer.write_all(b"\n")?;
+        self.written += 1;
+
+        Ok(())
+    }
+
+    /// Writes all objects of the iterator.
+    pub fn write_all<T: Serialize>(&mut self, items: impl IntoIterator<Item = T>) -> Result<()> {
+        for item in items {
+            self.write(&item)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of objects written so far.
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Flushes and returns the underlying writer.
+    pub fn into_inner(mut self) -> Result<W> {
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+}
+
+/// Writes all objects to `writer` and returns how many were written.
+pub fn write<W: Write, T: Serialize>(writer: W, items: impl IntoIterator<Item = T>) -> Result<usize> {
+    let mut writer = JsonlWriter::new(writer);
+    writer.write_all(items)?;
+    let written = writer.written();
+    writer.into_inner()?;
+
+    Ok(written)
+}
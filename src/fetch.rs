@@ -0,0 +1,224 @@
+//! Helpers for fetching many api objects at once
+#![warn(missing_docs)]
+use std::future::Future;
+
+use futures::stream::{self, StreamExt, TryStreamExt};
+
+use crate::{DeezerError, Result};
+
+/// Runs `fetch` for every item of `ids`, keeping at most `concurrency` requests in flight.
+///
+/// The results are returned in the order of `ids`.
+/// The first error is returned and aborts all pending requests.
+///
+/// Only the number of concurrent requests is limited here. Requests sent with a [`DeezerClient`](crate::DeezerClient)
+/// additionally wait for its [rate limiter](crate::DeezerClient::with_rate_limiter), if one is configured.
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::{fetch, DeezerClient, DeezerError};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), DeezerError> {
+/// let deezer = DeezerClient::new();
+///
+/// let albums = fetch::batched(vec![302127, 301775], 4, |id| deezer.album(id)).await?;
+/// # assert_eq!(albums.len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The order of `ids` is kept, independent of the order the requests finish in:
+///
+/// ```rust
+/// # use deezer::{fetch, DeezerError};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), DeezerError> {
+/// let doubled = fetch::batched(1..=5, 2, |id| async move { Ok(id * 2) }).await?;
+///
+/// assert_eq!(doubled, vec![2, 4, 6, 8, 10]);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn batched<I, F, Fut, T>(ids: I, concurrency: usize, fetch: F) -> Result<Vec<T>>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    stream::iter(ids)
+        .map(fetch)
+        .buffered(concurrency.max(1))
+        .try_collect()
+        .await
+}
+
+/// Outcome of a batch which continues after failing items, see [`settled()`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::{fetch, DeezerError};
+/// # #[tokio::main]
+/// # async fn main() {
+/// let result = fetch::settled(1..=4, 2, |id| async move {
+///     if id % 2 == 0 {
+///         Ok(id * 10)
+///     } else {
+///         Err(DeezerError::NotFound(id.to_string()))
+///     }
+/// }).await;
+///
+/// assert_eq!(result.succeeded, vec![20, 40]);
+/// assert_eq!(result.failed.iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![1, 3]);
+/// assert!(!result.is_complete());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BatchResult<T, I> {
+    /// The results of the successful items, in the order of the input
+    pub succeeded: Vec<T>,
+
+    /// The failed items and their errors, in the order of the input
+    pub failed: Vec<(I, DeezerError)>,
+}
+
+impl<T, I> Default for BatchResult<T, I> {
+    fn default() -> Self {
+        BatchResult {
+            succeeded: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+}
+
+impl<T, I> BatchResult<T, I> {
+    /// Returns whether all items succeeded.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    /// Returns the successful results, or the error of the first failed item.
+    pub fn into_result(self) -> Result<Vec<T>> {
+        match self.failed.into_iter().next() {
+            Some((_, err)) => Err(err),
+            None => Ok(self.succeeded),
+        }
+    }
+
+    /// Converts the failed items, e.g. to merge the results of batches over different id types.
+    pub fn map_failed<J>(self, mut map: impl FnMut(I) -> J) -> BatchResult<T, J> {
+        BatchResult {
+            succeeded: self.succeeded,
+            failed: self.failed.into_iter().map(|(id, err)| (map(id), err)).collect(),
+        }
+    }
+}
+
+/// Runs `fetch` for every item of `ids` like [`batched()`], but keeps going when items fail.
+///
+/// The errors are collected together with their item in the [`BatchResult`].
+pub async fn settled<I, F, Fut, T>(ids: I, concurrency: usize, mut fetch: F) -> BatchResult<T, I::Item>
+where
+    I: IntoIterator,
+    I::Item: Clone,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let results: Vec<_> = stream::iter(ids)
+        .map(|id| {
+            let request = fetch(id.clone());
+            async move { (id, request.await) }
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+
+    let mut batch = BatchResult::default();
+    for (id, result) in results {
+        match result {
+            Ok(value) => batch.succeeded.push(value),
+            Err(err) => batch.failed.push((id, err)),
+        }
+    }
+
+    batch
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+
+    /// Fetches the id after a delay which is shorter for higher ids, so requests finish out of order.
+    /// Tracks the maximum number of requests in flight in `max_in_flight`.
+    async fn fetch(id: u64, in_flight: &AtomicUsize, max_in_flight: &AtomicUsize) -> Result<u64> {
+        let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        max_in_flight.fetch_max(current, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(100 - id * 10)).await;
+        in_flight.fetch_sub(1, Ordering::SeqCst);
+        match id % 3 {
+            0 => Err(DeezerError::NotFound(id.to_string())),
+            _ => Ok(id * 10),
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn batched_keeps_the_order_and_limits_concurrency() {
+        let (in_flight, max_in_flight) = (AtomicUsize::new(0), AtomicUsize::new(0));
+
+        let values = batched([1, 2, 4, 5], 2, |id| fetch(id, &in_flight, &max_in_flight)).await.unwrap();
+
+        assert_eq!(values, vec![10, 20, 40, 50]);
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn batched_returns_the_first_error() {
+        let (in_flight, max_in_flight) = (AtomicUsize::new(0), AtomicUsize::new(0));
+
+        let err = batched(1..=7, 4, |id| fetch(id, &in_flight, &max_in_flight)).await.unwrap_err();
+
+        assert!(matches!(err, DeezerError::NotFound(id) if id == "3"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn zero_concurrency_runs_one_request_at_a_time() {
+        let (in_flight, max_in_flight) = (AtomicUsize::new(0), AtomicUsize::new(0));
+
+        let result = settled([1, 2], 0, |id| fetch(id, &in_flight, &max_in_flight)).await;
+
+        assert_eq!(result.succeeded, vec![10, 20]);
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn settled_collects_all_failures_in_order() {
+        let (in_flight, max_in_flight) = (AtomicUsize::new(0), AtomicUsize::new(0));
+
+        let result = settled(1..=7, 3, |id| fetch(id, &in_flight, &max_in_flight)).await;
+
+        assert_eq!(result.succeeded, vec![10, 20, 40, 50, 70]);
+        assert_eq!(result.failed.iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+        assert!(!result.is_complete());
+    }
+
+    #[test]
+    fn batch_results_convert_their_failures() {
+        let result = BatchResult {
+            succeeded: vec![10],
+            failed: vec![(3, DeezerError::NotFound("3".to_string())), (6, DeezerError::NotFound("6".to_string()))],
+        };
+
+        let result = result.map_failed(|id| id.to_string());
+        assert_eq!(result.failed.iter().map(|(id, _)| id.as_str()).collect::<Vec<_>>(), vec!["3", "6"]);
+        assert!(matches!(result.into_result(), Err(DeezerError::NotFound(id)) if id == "3"));
+
+        let complete: BatchResult<u64, u64> = BatchResult { succeeded: vec![10, 20], failed: Vec::new() };
+        assert!(complete.is_complete());
+        assert_eq!(complete.into_result().unwrap(), vec![10, 20]);
+    }
+}
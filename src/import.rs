@@ -0,0 +1,320 @@
+//! Matching of tracks from other services to Deezer tracks.
+//!
+//! Entries are resolved via their ISRC first. Entries without an ISRC, or whose ISRC has no
+//! matching Deezer track, are searched by artist and title, accepting the most similar result.
+//! Entries neither lookup finds a track for are reported as unmatched.
+//!
+//! With an access token, [`import_into_playlist()`] also creates a playlist of the matched tracks.
+#![warn(missing_docs)]
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashSet};
+use std::time::Duration;
+
+use crate::auth::Permission;
+use crate::models::{Isrc, PartialTrack, PlaylistId, Track, TrackId};
+use crate::search::SearchQuery;
+use crate::{fetch, DeezerClient, DeezerError, Result};
+
+/// Tolerated difference between the duration of an entry and the matched track.
+const DURATION_TOLERANCE: Duration = Duration::from_secs(5);
+
+/// Minimum similarity of both the artist and the title of a search result to match an entry.
+const MIN_SIMILARITY: f64 = 0.6;
+
+/// Number of search results considered when matching an entry by artist and title.
+const SEARCH_CANDIDATES: u32 = 10;
+
+/// Number of tracks added to the playlist per request by [`import_into_playlist()`].
+const PLAYLIST_CHUNK_SIZE: usize = 50;
+
+/// A track to import, e.g. a line of another service's playlist export.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct ImportEntry {
+    /// Name of the track's artist
+    pub artist: String,
+
+    /// Title of the track
+    pub title: String,
+
+    /// International Standard Recording Code of the track
+    pub isrc: Option<Isrc>,
+
+    /// Duration of the track, used to verify the match
+    pub duration: Option<Duration>,
+}
+
+impl ImportEntry {
+    /// Creates an entry with the given artist and title.
+    pub fn new(artist: impl Into<String>, title: impl Into<String>) -> Self {
+        ImportEntry {
+            artist: artist.into(),
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the ISRC of the entry.
+    pub fn with_isrc(mut self, isrc: impl Into<Isrc>) -> Self {
+        self.isrc = Some(isrc.into());
+        self
+    }
+
+    /// Sets the duration of the entry.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Returns how similar the track is to the entry, `None` when it isn't a plausible match.
+    ///
+    /// Artist and title are compared by their words, ignoring case, punctuation, featured artists
+    /// and suffixes like `(Remastered)` or `- Radio Edit`. Both have to be similar enough and the
+    /// duration must not differ by more than 5 seconds, when the entry has one.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use deezer::import::ImportEntry;
+    /// # use deezer::models::{PartialArtist, PartialTrack};
+    /// let entry = ImportEntry::new("Daft Punk feat. Romanthony", "One More Time (Radio Edit)")
+    ///     .with_duration(Duration::from_secs(320));
+    /// let mut track = PartialTrack::new(3135553, "One More Time");
+    /// track.artist = PartialArtist::new(27, "Daft Punk");
+    /// track.duration_in_seconds = 320;
+    ///
+    /// assert_eq!(entry.similarity(&track), Some(1.0));
+    ///
+    /// track.duration_in_seconds = 212;
+    /// assert_eq!(entry.similarity(&track), None);
+    ///
+    /// track.duration_in_seconds = 320;
+    /// track.title = "Aerodynamic".to_string();
+    /// assert_eq!(entry.similarity(&track), None);
+    /// ```
+    pub fn similarity(&self, track: &PartialTrack) -> Option<f64> {
+        if !self.matches_duration(track.duration()) {
+            return None;
+        }
+        let artist = similarity(&self.artist, &track.artist.name);
+        let title = std::iter::once(&track.title)
+            .chain(track.title_short.as_ref())
+            .map(|title| similarity(&self.title, title))
+            .fold(0.0, f64::max);
+        if artist < MIN_SIMILARITY || title < MIN_SIMILARITY {
+            return None;
+        }
+
+        Some((artist + title) / 2.0)
+    }
+
+    fn matches_duration(&self, track: Duration) -> bool {
+        match self.duration {
+            Some(duration) => duration.abs_diff(track) <= DURATION_TOLERANCE,
+            None => true,
+        }
+    }
+
+    fn search_query(&self) -> SearchQuery {
+        SearchQuery {
+            artist: Some(self.artist.clone()),
+            track: Some(self.title.clone()),
+            ..SearchQuery::default()
+        }
+    }
+}
+
+/// How the track of an [`ImportMatch`] was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MatchSource {
+    /// The ISRC of the entry identifies the track
+    Isrc,
+
+    /// The track is the most similar search result for the entry's artist and title
+    Search,
+}
+
+/// An entry and the Deezer track it was resolved to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportMatch {
+    /// The imported entry
+    pub entry: ImportEntry,
+
+    /// The matching Deezer track
+    pub track: Track,
+
+    /// How the track was found, search matches may be worth a review
+    pub source: MatchSource,
+}
+
+/// Result of [`resolve()`].
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// Entries with a matching Deezer track, in the order of the input
+    pub matched: Vec<ImportMatch>,
+
+    /// Entries without a matching Deezer track, in the order of the input
+    pub unmatched: Vec<ImportEntry>,
+
+    /// Entries whose lookup failed, e.g. due to network errors, in the order of the input
+    pub failed: Vec<(ImportEntry, DeezerError)>,
+}
+
+/// Result of [`import_into_playlist()`].
+#[derive(Debug)]
+pub struct PlaylistImport {
+    /// The id of the created playlist
+    pub playlist: PlaylistId,
+
+    /// The outcome of resolving the entries, the matched tracks have been added to the playlist
+    pub report: ImportReport,
+}
+
+/// Resolves the entries to Deezer tracks.
+///
+/// At most `concurrency` entries are looked up at the same time.
+/// A failing lookup doesn't abort the import, the entry is reported in [`ImportReport::failed`] instead.
+/// A track found via ISRC is rejected when its duration differs from the entry's by more than 5 seconds,
+/// the entry is searched by artist and title then, see [`ImportEntry::similarity()`].
+///
+/// # Examples
+/// ```rust
+/// # use deezer::{DeezerClient, DeezerError};
+/// # use deezer::import::{self, ImportEntry, MatchSource};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), DeezerError> {
+/// let deezer = DeezerClient::new();
+/// let entries = vec![
+///     ImportEntry::new("Daft Punk", "Harder, Better, Faster, Stronger").with_isrc("GBDUW0000059"),
+///     ImportEntry::new("Daft Punk", "Veridis Quo"),
+/// ];
+/// let report = import::resolve(&deezer, entries, 4).await;
+/// for matched in report.matched.iter().filter(|matched| matched.source == MatchSource::Search) {
+///     println!("please check {} - {}", matched.track.artist.name, matched.track.title);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn resolve(client: &DeezerClient, entries: impl IntoIterator<Item = ImportEntry>,
+                     concurrency: usize) -> ImportReport {
+    let resolved = fetch::settled(entries, concurrency, |entry| async move {
+        let track = resolve_entry(client, &entry).await?;
+
+        Ok((entry, track))
+    })
+    .await;
+
+    let mut report = ImportReport {
+        failed: resolved.failed,
+        ..ImportReport::default()
+    };
+    for (entry, track) in resolved.succeeded {
+        match track {
+            Some((track, source)) => report.matched.push(ImportMatch { entry, track, source }),
+            None => report.unmatched.push(entry),
+        }
+    }
+
+    report
+}
+
+/// Resolves the entries like [`resolve()`] and creates a playlist of the user containing the matched tracks.
+///
+/// The tracks are added in the order of the entries, entries resolving to the same track are added once.
+/// Requires the [`ManageLibrary`](Permission::ManageLibrary) permission, which is checked before resolving.
+///
+/// # Examples
+/// ```rust,no_run
+/// # use deezer::{DeezerClient, DeezerError};
+/// # use deezer::import::{self, ImportEntry};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), DeezerError> {
+/// let deezer = DeezerClient::new().with_access_token("access-token");
+/// let entries = vec![ImportEntry::new("Daft Punk", "Veridis Quo")];
+/// let import = import::import_into_playlist(&deezer, "Imported", entries, 4).await?;
+/// for entry in import.report.unmatched {
+///     println!("{} - {} is not on Deezer", entry.artist, entry.title);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn import_into_playlist(client: &DeezerClient, title: &str, entries: impl IntoIterator<Item = ImportEntry>,
+                                  concurrency: usize) -> Result<PlaylistImport> {
+    client.ensure_permission(Permission::ManageLibrary).await?;
+    let report = resolve(client, entries, concurrency).await;
+    let playlist = client.me_client().create_playlist(title).await?;
+
+    let mut added = HashSet::new();
+    let tracks: Vec<TrackId> = report
+        .matched
+        .iter()
+        .map(|matched| matched.track.id)
+        .filter(|id| added.insert(*id))
+        .collect();
+    for chunk in tracks.chunks(PLAYLIST_CHUNK_SIZE) {
+        client.add_playlist_tracks(playlist, chunk.iter().copied()).await?;
+    }
+
+    Ok(PlaylistImport { playlist, report })
+}
+
+/// Looks the entry up via its ISRC, falling back to searching its artist and title.
+async fn resolve_entry(client: &DeezerClient, entry: &ImportEntry) -> Result<Option<(Track, MatchSource)>> {
+    if let Some(isrc) = &entry.isrc {
+        let track = client.track_by_isrc(isrc.clone()).await?;
+        if let Some(track) = track.filter(|track| entry.matches_duration(track.duration())) {
+            return Ok(Some((track, MatchSource::Isrc)));
+        }
+    }
+    let candidates = client.search_tracks(&entry.search_query(), Some(SEARCH_CANDIDATES)).await?;
+    let best = candidates
+        .iter()
+        .filter_map(|candidate| entry.similarity(candidate).map(|similarity| (similarity, candidate)))
+        .max_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let track = match best {
+        Some((_, candidate)) => client.track(candidate.id).await?,
+        None => None,
+    };
+
+    Ok(track.map(|track| (track, MatchSource::Search)))
+}
+
+/// Dice coefficient of the normalized words of both names, between `0.0` and `1.0`.
+fn similarity(a: &str, b: &str) -> f64 {
+    let a = words(a);
+    let b = words(b);
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let common = a.intersection(&b).count();
+
+    (2 * common) as f64 / (a.len() + b.len()) as f64
+}
+
+/// The lowercase words of a name, without bracketed parts, `- ` suffixes and featured artists.
+fn words(name: &str) -> BTreeSet<String> {
+    let name = name.split(" - ").next().unwrap_or_default().to_lowercase();
+    let mut depth = 0u32;
+    let unbracketed: String = name
+        .chars()
+        .map(|c| match c {
+            '(' | '[' => {
+                depth += 1;
+                ' '
+            }
+            ')' | ']' => {
+                depth = depth.saturating_sub(1);
+                ' '
+            }
+            c if depth > 0 || !c.is_alphanumeric() => ' ',
+            c => c,
+        })
+        .collect();
+
+    unbracketed
+        .split_whitespace()
+        .take_while(|word| !matches!(*word, "feat" | "ft" | "featuring"))
+        .map(String::from)
+        .collect()
+}
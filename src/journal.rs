@@ -0,0 +1,104 @@
+//! Auditing of the requests sent to the api
+//!
+//! A [`Journal`] registered with [`DeezerClient::with_journal()`](crate::DeezerClient::with_journal)
+//! receives an entry for every request, e.g. to investigate quota usage of an app id shared by several teams.
+#![warn(missing_docs)]
+use std::fmt;
+#[cfg(feature = "jsonl")]
+use std::io::Write;
+#[cfg(feature = "jsonl")]
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use serde::Serialize;
+
+#[cfg(feature = "jsonl")]
+use crate::export::jsonl::JsonlWriter;
+
+/// A sent request, without the access token.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct JournalEntry {
+    /// The time the request was sent
+    #[serde(with = "crate::serde_helpers::timestamp")]
+    pub timestamp: SystemTime,
+
+    /// The http method, e.g. `GET`
+    pub method: String,
+
+    /// The requested url without query, e.g. `https://api.deezer.com/album/302127`
+    pub endpoint: String,
+
+    /// The query parameters, the access token and other secrets are left out
+    pub params: Vec<(String, String)>,
+
+    /// The time until the response headers were received
+    #[serde(serialize_with = "serialize_millis")]
+    pub duration: Duration,
+
+    /// The http status of the response, `None` when no response was received
+    pub status: Option<u16>,
+
+    /// The reason no response was received
+    pub error: Option<String>,
+}
+
+impl JournalEntry {
+    /// Returns whether a response with a success status was received.
+    pub fn is_success(&self) -> bool {
+        self.status.is_some_and(|status| (200..300).contains(&status))
+    }
+}
+
+fn serialize_millis<S: serde::Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_millis() as u64)
+}
+
+/// Sink receiving a [`JournalEntry`] for every request.
+///
+/// Entries are recorded while the request is in flight, slow sinks should hand them off, e.g. to a channel.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::sync::atomic::{AtomicUsize, Ordering};
+/// # use std::sync::Arc;
+/// # use deezer::DeezerClient;
+/// # use deezer::journal::JournalEntry;
+/// let requests = Arc::new(AtomicUsize::new(0));
+/// let counter = requests.clone();
+/// let deezer = DeezerClient::new().with_journal(move |entry: &JournalEntry| {
+///     counter.fetch_add(1, Ordering::Relaxed);
+/// });
+/// ```
+pub trait Journal: Send + Sync {
+    /// Records a sent request.
+    fn record(&self, entry: &JournalEntry);
+}
+
+impl fmt::Debug for dyn Journal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Journal")
+    }
+}
+
+impl<F> Journal for F
+where
+    F: Fn(&JournalEntry) + Send + Sync,
+{
+    fn record(&self, entry: &JournalEntry) {
+        self(entry)
+    }
+}
+
+/// Persists the entries as JSON Lines.
+///
+/// Failing writes are logged, they don't fail the request.
+#[cfg(feature = "jsonl")]
+impl<W: Write + Send> Journal for Mutex<JsonlWriter<W>> {
+    fn record(&self, entry: &JournalEntry) {
+        if let Err(err) = self.lock().unwrap().write(entry) {
+            log::warn!("Writing journal entry failed: {}", err);
+        }
+    }
+}
@@ -6,12 +6,66 @@
 //! Additionally each Deezer Object which is queryable by id implements the
 //! [`DeezerObject`](crate::models::DeezerObject) trait which allows direct fetching of the given
 //! object via [`DeezerObject::get()`](crate::models::DeezerObject::get).
+//!
+//! # Features
+//!
+//! * `chrono` - Adds accessors returning the dates of api objects as [`chrono`] types.
+//! * `disk-cache` - Allows the [`ArtworkCache`](artwork::ArtworkCache) to store images on disk.
+//! * `jsonl` - Adds an exporter writing api objects as JSON Lines, see `export::jsonl`, which can also persist the [`journal`].
+//! * `mpris` - Adds conversions of tracks into MPRIS metadata, see [`mpris`].
+//! * `otel` - Wraps every api request in a `tracing` span with the OpenTelemetry http client attributes.
+//! * `tags` - Adds conversions of tracks and albums into audio tag metadata, see [`tags`].
+//! * `tower` - Implements `tower::Service` for the [`DeezerClient`], see `service`.
+//! * `url` - Adds typed access to the links of api objects, see `models::DeezerUrls`.
+
+// Allows the derive macros to refer to this crate as `::deezer` from within.
+extern crate self as deezer;
 
+pub mod artwork;
+pub mod auth;
+pub mod availability;
+mod cache;
+pub mod cancel;
+pub mod catalog;
+pub mod charts;
 mod client;
+#[cfg(feature = "chrono")]
+mod dates;
 mod error;
+pub mod export;
+pub mod fetch;
+pub mod import;
+pub mod journal;
+pub mod links;
+mod logging;
+#[cfg(test)]
+mod mock_server;
+pub mod me;
 pub mod models;
+#[cfg(feature = "otel")]
+mod otel;
+mod params;
+pub mod queue;
+pub mod rate_limit;
+pub mod redirect;
+mod request;
+pub mod resolver;
+pub mod search;
+#[cfg(feature = "tower")]
+pub mod service;
+pub mod serde_helpers;
+pub mod snapshot;
+pub mod sync;
+pub mod watch;
+#[cfg(feature = "mpris")]
+pub mod mpris;
+#[cfg(feature = "tags")]
+pub mod tags;
 
 pub use self::client::DeezerClient;
 pub use self::error::DeezerError;
+pub use self::params::{ParamValue, Params};
+pub use self::request::RequestBuilder;
+pub use reqwest::Method;
 
 pub(crate) type Result<T> = std::result::Result<T, DeezerError>;
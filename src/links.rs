@@ -0,0 +1,96 @@
+//! Parsing of links to Deezer objects, as shared from the apps and the website.
+#![warn(missing_docs)]
+use reqwest::redirect::Policy;
+use reqwest::{header, Url};
+
+use crate::models::AnyId;
+use crate::Result;
+
+/// Hosts of the short links shared by the mobile apps
+const SHORT_LINK_HOSTS: [&str; 2] = ["deezer.page.link", "link.deezer.com"];
+
+/// Maximum number of redirects followed when resolving a short link
+const MAX_REDIRECTS: usize = 5;
+
+/// Parses a link to an object on deezer.com.
+///
+/// Both links with and without a language prefix are supported.
+/// Returns `None` for links to other sites or pages without an object.
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::links;
+/// # use deezer::models::{AnyId, TrackId};
+/// let id = links::parse_url("https://www.deezer.com/en/track/3135556?utm_source=deezer");
+///
+/// assert_eq!(id, Some(AnyId::Track(TrackId::new(3135556))));
+/// assert_eq!(links::parse_url("https://www.deezer.com/en/"), None);
+/// ```
+pub fn parse_url(url: &str) -> Option<AnyId> {
+    let url = Url::parse(url).ok()?;
+    let host = url.host_str()?;
+    if host != "deezer.com" && !host.ends_with(".deezer.com") {
+        return None;
+    }
+    let mut segments = url.path_segments()?.filter(|segment| !segment.is_empty());
+    let mut kind = segments.next()?;
+    if kind.len() == 2 {
+        // language prefix, e.g. `/en/`
+        kind = segments.next()?;
+    }
+    let id = segments.next()?.parse().ok()?;
+
+    AnyId::from_kind(kind, id)
+}
+
+/// Returns `true` for short links which have to be resolved with [`resolve_url()`].
+pub fn is_short_link(url: &str) -> bool {
+    Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(|host| SHORT_LINK_HOSTS.contains(&host)))
+        .unwrap_or_default()
+}
+
+/// Parses a link to a Deezer object, following the redirects of short links.
+///
+/// Only the redirect targets are requested, the linked pages are not downloaded.
+///
+/// # Examples
+/// ```rust
+/// # use deezer::{links, DeezerError};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), DeezerError> {
+/// let id = links::resolve_url("https://www.deezer.com/album/302127").await?;
+/// # assert_eq!(id.unwrap().get(), 302127);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn resolve_url(url: &str) -> Result<Option<AnyId>> {
+    if !is_short_link(url) {
+        return Ok(parse_url(url));
+    }
+    let client = reqwest::Client::builder().redirect(Policy::none()).build()?;
+    let mut url = url.to_string();
+    for _ in 0..MAX_REDIRECTS {
+        let res = client.head(&url).send().await?;
+        let location = res
+            .headers()
+            .get(header::LOCATION)
+            .and_then(|location| location.to_str().ok());
+        let location = match location {
+            Some(location) => location,
+            None => return Ok(None),
+        };
+        // relative redirects are resolved against the current url
+        url = match Url::parse(&url).and_then(|current| current.join(location)) {
+            Ok(next) => next.to_string(),
+            Err(_) => return Ok(None),
+        };
+        if !is_short_link(&url) {
+            return Ok(parse_url(&url));
+        }
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,134 @@
+//! Logging of the api requests via the `log` crate.
+use std::error::Error;
+use std::time::Duration;
+
+use reqwest::{Method, Response, Url};
+
+/// Log target of the request log, allows filtering it independently of the other messages of this crate.
+const TARGET: &str = "deezer::request";
+
+/// Query parameters whose values are replaced before logging
+const SECRET_PARAMS: &[&str] = &["access_token", "secret", "code"];
+
+/// Logs one line of `key=value` pairs for every api request.
+#[derive(Debug)]
+pub(crate) struct RequestLog {
+    level: log::Level,
+}
+
+impl RequestLog {
+    pub(crate) fn new(level: log::Level) -> Self {
+        RequestLog { level }
+    }
+
+    pub(crate) fn log(&self, method: &Method, url: &Url, resend_count: u32,
+                      response: &reqwest::Result<Response>, elapsed: Duration) {
+        if !log::log_enabled!(target: TARGET, self.level) {
+            return;
+        }
+        let url = redact(url);
+        let elapsed = elapsed.as_millis();
+        match response {
+            Ok(response) => log::log!(
+                target: TARGET,
+                self.level,
+                "method={} url={} status={} elapsed_ms={} resend_count={}",
+                method, url, response.status().as_u16(), elapsed, resend_count
+            ),
+            // the error itself is not logged as its message contains the url including the access token
+            Err(err) => log::log!(
+                target: TARGET,
+                self.level,
+                "method={} url={} error={:?} elapsed_ms={} resend_count={}",
+                method, url, describe(err), elapsed, resend_count
+            ),
+        }
+    }
+}
+
+/// Returns the query parameters of the url, leaving out the secret ones.
+pub(crate) fn public_params(url: &Url) -> Vec<(String, String)> {
+    url.query_pairs()
+        .filter(|(key, _)| !SECRET_PARAMS.contains(&key.as_ref()))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect()
+}
+
+/// Returns the url with the values of secret query parameters, like the access token, replaced.
+///
+/// Urls without secrets are returned unchanged.
+pub(crate) fn redact(url: &Url) -> Url {
+    let is_secret = |key: &str| SECRET_PARAMS.contains(&key);
+    if !url.query_pairs().any(|(key, _)| is_secret(&key)) {
+        return url.clone();
+    }
+    let mut redacted = url.clone();
+    redacted.query_pairs_mut()
+        .clear()
+        .extend_pairs(url.query_pairs().map(|(key, value)| {
+            let value = if is_secret(&key) { "REDACTED".into() } else { value };
+            (key, value)
+        }));
+
+    redacted
+}
+
+/// Describes the error without its url, which may contain the access token.
+pub(crate) fn describe(err: &reqwest::Error) -> String {
+    let kind = if err.is_timeout() {
+        "timeout"
+    } else if err.is_connect() {
+        "connect"
+    } else if err.is_redirect() {
+        "redirect"
+    } else if err.is_body() {
+        "body"
+    } else {
+        "request"
+    };
+    match err.source() {
+        Some(source) => format!("{}: {}", kind, source),
+        None => kind.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(query: &str) -> Url {
+        Url::parse(&format!("https://api.deezer.com/user/me?{}", query)).unwrap()
+    }
+
+    #[test]
+    fn secrets_are_redacted() {
+        let redacted = redact(&url("access_token=token&limit=10&code=abc&secret=s3cr3t"));
+
+        assert_eq!(redacted.as_str(), "https://api.deezer.com/user/me?access_token=REDACTED&limit=10&code=REDACTED&secret=REDACTED");
+    }
+
+    #[test]
+    fn urls_without_secrets_are_unchanged() {
+        for url in [url("limit=10&q=daft%20punk"), Url::parse("https://api.deezer.com/track/3135556").unwrap()] {
+            assert_eq!(redact(&url), url);
+        }
+    }
+
+    #[test]
+    fn encoded_secrets_are_redacted() {
+        let redacted = redact(&url("q=a%26b&access%5Ftoken=token"));
+
+        assert!(!redacted.as_str().contains("token=token"));
+        assert_eq!(public_params(&redacted), vec![("q".to_string(), "a&b".to_string())]);
+    }
+
+    #[test]
+    fn public_params_leave_out_secrets() {
+        let params = public_params(&url("access_token=token&index=25&limit=10"));
+
+        assert_eq!(params, vec![
+            ("index".to_string(), "25".to_string()),
+            ("limit".to_string(), "10".to_string()),
+        ]);
+    }
+}
@@ -0,0 +1,413 @@
+//! The resources of the user authenticated by the access token of a [`DeezerClient`]
+#![warn(missing_docs)]
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::auth::Permission;
+use crate::fetch::{self, BatchResult};
+use crate::models::{AlbumId, ArtistId, PartialAlbum, PartialArtist, PartialPlaylist, PartialTrack, PlaylistId, TrackId, User};
+use crate::{DeezerClient, Method, Params, Result};
+
+/// Number of favorites added between two progress reports
+const BULK_CHUNK_SIZE: usize = 25;
+
+/// Maximum number of favorites added concurrently
+const BULK_CONCURRENCY: usize = 4;
+
+/// Number of times a favorite is sent again after a transient error
+const BULK_RETRIES: u32 = 2;
+
+/// Delay before the first retry, doubled for every further retry
+const BULK_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Delay before retrying a request rejected by the quota, deezer counts requests per 5 seconds
+const BULK_QUOTA_DELAY: Duration = Duration::from_secs(5);
+
+/// Pause between two chunks, keeping a bulk operation below deezer's quota of 50 requests per 5 seconds
+const BULK_CHUNK_PAUSE: Duration = Duration::from_secs(3);
+
+/// Progress of a bulk operation like [`MeClient::add_favorite_tracks()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BulkProgress {
+    /// The number of processed items, including failed ones
+    pub done: usize,
+
+    /// The number of items which failed
+    pub failed: usize,
+
+    /// The number of items in total
+    pub total: usize,
+}
+
+/// Entrypoint for the resources of the authenticated user, see [`DeezerClient::me_client()`].
+///
+/// All requests fail with [`DeezerError::InsufficientPermission`](crate::DeezerError::InsufficientPermission)
+/// when the client has no access token.
+///
+/// # Examples
+/// ```rust,no_run
+/// # use deezer::{DeezerClient, DeezerError};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), DeezerError> {
+/// let deezer = DeezerClient::new().with_access_token("access-token");
+/// let me = deezer.me_client();
+///
+/// let user = me.user().await?;
+/// let playlists = me.playlists().await?;
+/// println!("{} has {} playlists", user.name, playlists.len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MeClient {
+    client: DeezerClient,
+}
+
+impl MeClient {
+    pub(crate) fn new(client: DeezerClient) -> Self {
+        MeClient { client }
+    }
+
+    /// Returns the authenticated [`User`], see [`DeezerClient::me()`].
+    pub async fn user(&self) -> Result<User> {
+        self.client.me().await
+    }
+
+    /// Returns the permissions the user granted to the access token.
+    ///
+    /// Permissions unknown to this crate are skipped.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/permissions)
+    pub async fn permissions(&self) -> Result<Vec<Permission>> {
+        #[derive(Deserialize)]
+        struct Permissions {
+            permissions: BTreeMap<String, bool>,
+        }
+
+        let response: Permissions = self.client.request(Method::GET, "user/me/permissions").json().await?;
+
+        Ok(response.permissions
+            .into_iter()
+            .filter(|(_, granted)| *granted)
+            .filter_map(|(name, _)| name.parse().ok())
+            .collect())
+    }
+
+    /// Returns all favorite tracks of the user.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/tracks)
+    pub async fn favorite_tracks(&self) -> Result<Vec<PartialTrack>> {
+        self.get_all(&["tracks"]).await
+    }
+
+    /// Adds the track with the given id to the loved tracks of the user.
+    ///
+    /// Requires the [`ManageLibrary`](Permission::ManageLibrary) permission,
+    /// use [`add_favorite_tracks()`](MeClient::add_favorite_tracks) for adding many tracks.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/tracks)
+    pub async fn add_favorite_track(&self, id: impl Into<TrackId>) -> Result<()> {
+        self.client.ensure_permission(Permission::ManageLibrary).await?;
+
+        self.add_favorite("tracks", "track_id", id.into().get()).await
+    }
+
+    /// Removes the track with the given id from the loved tracks of the user.
+    ///
+    /// Requires the [`DeleteLibrary`](Permission::DeleteLibrary) permission.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/tracks)
+    pub async fn remove_favorite_track(&self, id: impl Into<TrackId>) -> Result<()> {
+        self.client.ensure_permission(Permission::DeleteLibrary).await?;
+
+        self.remove_favorite("tracks", "track_id", id.into().get()).await
+    }
+
+    /// Returns all favorite albums of the user.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/albums)
+    pub async fn favorite_albums(&self) -> Result<Vec<PartialAlbum>> {
+        self.get_all(&["albums"]).await
+    }
+
+    /// Adds the album with the given id to the favorites of the user.
+    ///
+    /// Requires the [`ManageLibrary`](Permission::ManageLibrary) permission.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/albums)
+    pub async fn add_favorite_album(&self, id: impl Into<AlbumId>) -> Result<()> {
+        self.client.ensure_permission(Permission::ManageLibrary).await?;
+
+        self.add_favorite("albums", "album_id", id.into().get()).await
+    }
+
+    /// Removes the album with the given id from the favorites of the user.
+    ///
+    /// Requires the [`DeleteLibrary`](Permission::DeleteLibrary) permission.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/albums)
+    pub async fn remove_favorite_album(&self, id: impl Into<AlbumId>) -> Result<()> {
+        self.client.ensure_permission(Permission::DeleteLibrary).await?;
+
+        self.remove_favorite("albums", "album_id", id.into().get()).await
+    }
+
+    /// Returns all favorite artists of the user.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/artists)
+    pub async fn favorite_artists(&self) -> Result<Vec<PartialArtist>> {
+        self.get_all(&["artists"]).await
+    }
+
+    /// Follows the artist with the given id, adding it to the favorite artists of the user.
+    ///
+    /// Requires the [`ManageLibrary`](Permission::ManageLibrary) permission.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/artists)
+    pub async fn add_favorite_artist(&self, id: impl Into<ArtistId>) -> Result<()> {
+        self.client.ensure_permission(Permission::ManageLibrary).await?;
+
+        self.add_favorite("artists", "artist_id", id.into().get()).await
+    }
+
+    /// Unfollows the artist with the given id.
+    ///
+    /// Requires the [`DeleteLibrary`](Permission::DeleteLibrary) permission.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/artists)
+    pub async fn remove_favorite_artist(&self, id: impl Into<ArtistId>) -> Result<()> {
+        self.client.ensure_permission(Permission::DeleteLibrary).await?;
+
+        self.remove_favorite("artists", "artist_id", id.into().get()).await
+    }
+
+    /// Returns all playlists of the user, including the favorite playlists of other users.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/playlists)
+    pub async fn playlists(&self) -> Result<Vec<PartialPlaylist>> {
+        self.get_all(&["playlists"]).await
+    }
+
+    /// Subscribes to the playlist of another user with the given id, adding it to the [`playlists()`](MeClient::playlists).
+    ///
+    /// Requires the [`ManageLibrary`](Permission::ManageLibrary) permission.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/playlists)
+    pub async fn add_favorite_playlist(&self, id: impl Into<PlaylistId>) -> Result<()> {
+        self.client.ensure_permission(Permission::ManageLibrary).await?;
+
+        self.add_favorite("playlists", "playlist_id", id.into().get()).await
+    }
+
+    /// Unsubscribes from the playlist with the given id.
+    ///
+    /// Requires the [`DeleteLibrary`](Permission::DeleteLibrary) permission.
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/playlists)
+    pub async fn remove_favorite_playlist(&self, id: impl Into<PlaylistId>) -> Result<()> {
+        self.client.ensure_permission(Permission::DeleteLibrary).await?;
+
+        self.remove_favorite("playlists", "playlist_id", id.into().get()).await
+    }
+
+    /// Creates a new playlist of the user and returns its id.
+    ///
+    /// Requires the [`ManageLibrary`](Permission::ManageLibrary) permission.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new().with_access_token("access-token");
+    /// let playlist = deezer.me_client().create_playlist("Road Trip").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/playlists)
+    pub async fn create_playlist(&self, title: &str) -> Result<PlaylistId> {
+        #[derive(Deserialize)]
+        struct Created {
+            id: PlaylistId,
+        }
+
+        self.client.ensure_permission(Permission::ManageLibrary).await?;
+        let url = self.client.api_url(&["user", "me", "playlists"]);
+        let created: Created = self.client.post(url, &Params::new().with("title", title)).await?;
+
+        Ok(created.id)
+    }
+
+    /// Adds all tracks to the favorites of the user, e.g. when migrating loved tracks from another service.
+    ///
+    /// The tracks are added in chunks with a pause in between to stay below the request quota,
+    /// `progress` is called after each chunk.
+    /// Requests failing with transient errors, including an exceeded quota, are retried.
+    /// Tracks which still fail to be added are returned in [`BatchResult::failed`] without aborting the import.
+    /// Requires the [`ManageLibrary`](Permission::ManageLibrary) permission.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new().with_access_token("access-token");
+    /// let result = deezer.me_client()
+    ///     .add_favorite_tracks(vec![3135556, 3135553], |progress| {
+    ///         println!("{}/{}", progress.done, progress.total);
+    ///     })
+    ///     .await?;
+    /// println!("{} tracks could not be added", result.failed.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [Deezer Api Documentation](https://developers.deezer.com/api/user/tracks)
+    pub async fn add_favorite_tracks<T>(&self, ids: impl IntoIterator<Item = T>,
+                                        mut progress: impl FnMut(BulkProgress)) -> Result<BatchResult<TrackId, TrackId>>
+        where
+            T: Into<TrackId>,
+    {
+        self.client.ensure_permission(Permission::ManageLibrary).await?;
+        let ids: Vec<TrackId> = ids.into_iter().map(Into::into).collect();
+        let mut result = BatchResult::default();
+        for (index, chunk) in ids.chunks(BULK_CHUNK_SIZE).enumerate() {
+            if index > 0 {
+                tokio::time::sleep(BULK_CHUNK_PAUSE).await;
+            }
+            let added = fetch::settled(chunk.iter().copied(), BULK_CONCURRENCY, |id| async move {
+                self.add_favorite("tracks", "track_id", id.get()).await.map(|_| id)
+            })
+            .await;
+            result.succeeded.extend(added.succeeded);
+            result.failed.extend(added.failed);
+            progress(BulkProgress {
+                done: result.succeeded.len() + result.failed.len(),
+                failed: result.failed.len(),
+                total: ids.len(),
+            });
+        }
+
+        Ok(result)
+    }
+
+    /// Adds a single favorite, retrying transient errors.
+    async fn add_favorite(&self, kind: &str, param: &'static str, id: u64) -> Result<()> {
+        let url = self.client.api_url(&["user", "me", kind]);
+        let params = Params::new().with(param, id);
+
+        retry_transient(|| self.client.post::<bool>(url.clone(), &params)).await.map(drop)
+    }
+
+    /// Removes a single favorite.
+    async fn remove_favorite(&self, kind: &str, param: &'static str, id: u64) -> Result<()> {
+        let url = self.client.api_url(&["user", "me", kind]);
+        let _: bool = self.client.delete(url, &Params::new().with(param, id)).await?;
+
+        Ok(())
+    }
+
+    async fn get_all<T: serde::de::DeserializeOwned>(&self, path: &[&str]) -> Result<Vec<T>> {
+        let url = self.client.api_url(["user", "me"].iter().chain(path));
+
+        self.client.get_all_pages(url).await
+    }
+}
+
+/// Sends the request until it succeeds, fails permanently or has been retried [`BULK_RETRIES`] times.
+///
+/// Requests rejected by the quota are retried once it has been reset, other transient errors
+/// after an exponential backoff.
+async fn retry_transient<T, F, Fut>(mut request: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+{
+    let mut retries = 0;
+    loop {
+        match request().await {
+            Err(err) if err.is_transient() && retries < BULK_RETRIES => {
+                let delay = if err.is_rate_limited() {
+                    BULK_QUOTA_DELAY
+                } else {
+                    BULK_RETRY_DELAY * 2u32.pow(retries)
+                };
+                log::debug!("Request failed ({}), retrying in {:?}", err, delay);
+                tokio::time::sleep(delay).await;
+                retries += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use tokio::time::Instant;
+
+    use super::*;
+    use crate::DeezerError;
+
+    fn api_error(code: u32) -> DeezerError {
+        DeezerError::ApiError { code, message: String::new() }
+    }
+
+    /// Fails with the given errors in order, succeeding once they are used up.
+    async fn attempt(attempts: &Cell<usize>, errors: &[u32]) -> Result<usize> {
+        let attempt = attempts.get();
+        attempts.set(attempt + 1);
+        match errors.get(attempt) {
+            Some(code) => Err(api_error(*code)),
+            None => Ok(attempt),
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retries_exceeded_quota_after_it_was_reset() {
+        let attempts = Cell::new(0);
+        let started = Instant::now();
+
+        let result = retry_transient(|| attempt(&attempts, &[4, 4])).await;
+
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(started.elapsed(), BULK_QUOTA_DELAY * 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retries_overload_with_backoff() {
+        let attempts = Cell::new(0);
+        let started = Instant::now();
+
+        let result = retry_transient(|| attempt(&attempts, &[700, 700])).await;
+
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(started.elapsed(), BULK_RETRY_DELAY * 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn gives_up_after_the_last_retry() {
+        let attempts = Cell::new(0);
+
+        let result = retry_transient(|| attempt(&attempts, &[4, 4, 4, 4])).await;
+
+        assert!(matches!(result, Err(DeezerError::ApiError { code: 4, .. })));
+        assert_eq!(attempts.get(), BULK_RETRIES as usize + 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn does_not_retry_permanent_errors() {
+        let attempts = Cell::new(0);
+
+        let result = retry_transient(|| attempt(&attempts, &[200])).await;
+
+        assert!(matches!(result, Err(DeezerError::ApiError { code: 200, .. })));
+        assert_eq!(attempts.get(), 1);
+    }
+}
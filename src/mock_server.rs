@@ -0,0 +1,158 @@
+//! A minimal http server answering requests with canned responses, for tests without network access.
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use reqwest::Url;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// A request received by the [`MockServer`].
+#[derive(Debug, Clone)]
+pub(crate) struct ReceivedRequest {
+    pub(crate) method: String,
+    pub(crate) url: Url,
+    pub(crate) headers: HashMap<String, String>,
+    pub(crate) body: Vec<u8>,
+}
+
+impl ReceivedRequest {
+    /// Returns the value of the query parameter, `None` when it's missing.
+    pub(crate) fn query(&self, key: &str) -> Option<String> {
+        self.url
+            .query_pairs()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.into_owned())
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    responses: HashMap<String, Vec<String>>,
+    requests: Vec<ReceivedRequest>,
+}
+
+/// Answers requests to registered paths with json bodies, other paths with `404`.
+///
+/// Every request is recorded and can be inspected with [`requests()`](MockServer::requests).
+#[derive(Debug, Clone)]
+pub(crate) struct MockServer {
+    url: Url,
+    state: Arc<Mutex<State>>,
+}
+
+impl MockServer {
+    /// Starts the server on a random local port, it runs until the test runtime shuts down.
+    pub(crate) async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        let server = MockServer {
+            url,
+            state: Arc::default(),
+        };
+        let state = Arc::clone(&server.state);
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(handle(stream, Arc::clone(&state)));
+            }
+        });
+
+        server
+    }
+
+    /// The root url of the server.
+    pub(crate) fn url(&self) -> Url {
+        self.url.clone()
+    }
+
+    /// Answers the next request to `path` with `body`.
+    ///
+    /// Responses registered for the same path are used in order, the last one is repeated.
+    pub(crate) fn respond(&self, path: &str, body: impl Into<String>) -> &Self {
+        self.state
+            .lock()
+            .unwrap()
+            .responses
+            .entry(path.to_string())
+            .or_default()
+            .push(body.into());
+        self
+    }
+
+    /// Returns the received requests in the order they arrived.
+    pub(crate) fn requests(&self) -> Vec<ReceivedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Returns the received requests to `path`.
+    pub(crate) fn requests_to(&self, path: &str) -> Vec<ReceivedRequest> {
+        self.requests()
+            .into_iter()
+            .filter(|request| request.url.path() == path)
+            .collect()
+    }
+}
+
+async fn handle(mut stream: TcpStream, state: Arc<Mutex<State>>) {
+    let mut data = Vec::new();
+    let mut buffer = [0; 4096];
+    let header_end = loop {
+        if let Some(end) = data.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end;
+        }
+        match stream.read(&mut buffer).await {
+            Ok(0) | Err(_) => return,
+            Ok(read) => data.extend_from_slice(&buffer[..read]),
+        }
+    };
+    let head = String::from_utf8_lossy(&data[..header_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split(' ');
+    let method = request_line.next().unwrap_or_default().to_string();
+    let target = request_line.next().unwrap_or_default().to_string();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+        .collect();
+    let length: usize = headers
+        .get("content-length")
+        .and_then(|length| length.parse().ok())
+        .unwrap_or_default();
+    let mut body = data.split_off(header_end + 4);
+    while body.len() < length {
+        match stream.read(&mut buffer).await {
+            Ok(0) | Err(_) => return,
+            Ok(read) => body.extend_from_slice(&buffer[..read]),
+        }
+    }
+
+    let url = Url::parse("http://localhost").unwrap().join(&target).unwrap();
+    let response = {
+        let mut state = state.lock().unwrap();
+        let response = state.responses.get_mut(url.path()).map(|responses| {
+            if responses.len() > 1 {
+                responses.remove(0)
+            } else {
+                responses[0].clone()
+            }
+        });
+        state.requests.push(ReceivedRequest {
+            method,
+            url,
+            headers,
+            body,
+        });
+        response
+    };
+    let (status, body) = match response {
+        Some(body) => ("200 OK", body),
+        None => ("404 Not Found", String::new()),
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
@@ -1,10 +1,15 @@
 //! [Album API](https://developers.deezer.com/api/album)
 #![warn(missing_docs)]
+use std::fmt;
+use std::time::Duration;
 
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
 
-use crate::models::{Artist, ContributorArtist, DeezerArray, DeezerObject, DeezerUpcObject, Genre, Track, Upc};
-use crate::Result;
+use crate::models::image::cdn_image_url;
+use crate::models::{AlbumId, ContributorArtist, DeezerArray, DeezerObject, DeezerPartial, DeezerUpcObject, ExplicitContentLevel, Genre, GenreId, ImageSize, PartialArtist, PartialTrack, Upc};
+use crate::{DeezerClient, Result};
 
 /// Contains all the information provided for an Album.
 ///
@@ -41,16 +46,20 @@ use crate::Result;
 /// # }
 ///
 /// ```
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct Album {
     /// `The Deezer album id`
-    pub id: u64,
+    pub id: AlbumId,
 
     /// `The album title`
     pub title: String,
 
     /// `The album UPC`
-    pub upc: String,
+    ///
+    /// `Not returned in every market`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upc: Option<String>,
 
     /// `The url of the album on Deezer`
     pub link: String,
@@ -74,6 +83,10 @@ pub struct Album {
     /// `The url of the album's cover in size xl.`
     pub cover_xl: String,
 
+    /// `The md5 hash of the album's cover image`
+    #[serde(default, deserialize_with = "crate::serde_helpers::empty_string_as_none", skip_serializing_if = "Option::is_none")]
+    pub md5_image: Option<String>,
+
     /// `The album's first genre id (You should use the genre list instead).`
     pub genre_id: Option<i32>,
 
@@ -100,24 +113,33 @@ pub struct Album {
     pub release_date: String,
 
     /// `The record type of the album (EP / ALBUM / etc..)`
-    pub record_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub record_type: Option<RecordType>,
 
     /// `Whether it's available right now`
     pub available: bool,
 
     /// `Return an alternative album object if the current album is not available`
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     #[serde(rename = "alternative")]
     pub alternative_album: Option<Box<Album>>,
 
     /// `API Link to the tracklist of this album`
     #[serde(rename = "tracklist")]
-    tracklist_api_url: String,
+    pub tracklist_api_url: String,
 
     /// `Whether the album contains explicit lyrics`
     #[serde(rename = "explicit_lyrics")]
     pub has_explicit_lyrics: bool,
 
+    /// `The explicit content level of the album's lyrics`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explicit_content_lyrics: Option<ExplicitContentLevel>,
+
+    /// `The explicit content level of the album's cover`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explicit_content_cover: Option<ExplicitContentLevel>,
+
     /// `Return a list of contributors on the album`
     pub contributors: Vec<ContributorArtist>,
 
@@ -129,159 +151,457 @@ pub struct Album {
 }
 
 impl DeezerObject for Album {
-    fn get_api_url(id: u64) -> String {
+    type Id = AlbumId;
+
+    fn get_api_url(id: AlbumId) -> String {
         format!("album/{}", id)
     }
 }
 
+/// Formats the album as `Artist – Title (Year)`, the year is left out when the release date is unknown.
+impl fmt::Display for Album {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} – {}", self.artist.name, self.title)?;
+        match self.release_date.get(..4) {
+            Some(year) if year != "0000" => write!(f, " ({})", year),
+            _ => Ok(()),
+        }
+    }
+}
+
 impl DeezerUpcObject for Album {
     fn get_api_url(upc: Upc) -> String {
         format!("album/upc:{}", upc)
     }
 }
 
-/// Subset of [`Artist`].
-///
-/// Use [`get_full()`] for the full [`Artist`].
-///
-/// [`get_full()`]: AlbumArtist::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct AlbumArtist {
-    /// `The artist's Deezer id`
-    pub id: u64,
+impl Album {
+    /// Creates an album with the given id and title, all other fields are left empty.
+    ///
+    /// Models are `#[non_exhaustive]`, so this is the way to build one outside of this crate,
+    /// e.g. for test fixtures. The remaining fields can be assigned afterwards.
+    ///
+    /// ```rust
+    /// # use deezer::models::*;
+    /// let mut album = Album::new(302127, "Discovery");
+    /// album.record_type = Some(RecordType::Album);
+    /// album.tracks = vec![PartialTrack::new(3135556, "Harder, Better, Faster, Stronger")].into();
+    ///
+    /// assert_eq!(album.id, 302127);
+    /// assert_eq!(album.tracks.len(), 1);
+    /// ```
+    pub fn new(id: impl Into<AlbumId>, title: impl Into<String>) -> Self {
+        Album {
+            id: id.into(),
+            title: title.into(),
+            ..Default::default()
+        }
+    }
 
-    /// `The artist's name`
-    pub name: String,
+    /// Returns the url of the cover in the given size.
+    pub fn image_url(&self, size: ImageSize) -> &str {
+        match size {
+            ImageSize::Small => &self.cover_small,
+            ImageSize::Medium => &self.cover_medium,
+            ImageSize::Big => &self.cover_big,
+            ImageSize::Xl => &self.cover_xl,
+        }
+    }
 
-    /// `The url of the artist picture`
-    pub picture: String,
+    /// Returns the url of the cover in a custom size of `pixels`x`pixels`.
+    ///
+    /// Deezer's image cdn renders covers in any size, which allows e.g. high-dpi artwork
+    /// beyond [`ImageSize::Xl`]. Returns `None` when the album has no [`md5_image`](Album::md5_image).
+    ///
+    /// ```rust
+    /// # use deezer::models::*;
+    /// let mut album = Album::new(302127, "Discovery");
+    /// album.md5_image = Some("2e018122cb56986277102d2041a592c8".to_string());
+    ///
+    /// assert_eq!(
+    ///     album.cover_url(1400).as_deref(),
+    ///     Some("https://e-cdns-images.dzcdn.net/images/cover/2e018122cb56986277102d2041a592c8/1400x1400-000000-80-0-0.jpg")
+    /// );
+    /// ```
+    pub fn cover_url(&self, pixels: u32) -> Option<String> {
+        self.md5_image
+            .as_deref()
+            .map(|md5| cdn_image_url("cover", md5, pixels))
+    }
 
-    /// `The url of the artist picture in size small`
-    pub picture_small: String,
+    /// Returns the album's duration.
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(self.duration_in_seconds)
+    }
 
-    /// `The url of the artist picture in size medium`
-    pub picture_medium: String,
+    /// Returns the parsed [`release_date`](Album::release_date).
+    ///
+    /// Returns `None` when the release date is unknown.
+    #[cfg(feature = "chrono")]
+    pub fn released_on(&self) -> Option<NaiveDate> {
+        crate::dates::parse_date(&self.release_date)
+    }
 
-    /// `The url of the artist picture in size big`
-    pub picture_big: String,
+    /// Returns an album which is available in the current region.
+    ///
+    /// This is the album itself when it's [`available`](Album::available),
+    /// otherwise its [`alternative_album`](Album::alternative_album) is fetched.
+    /// Returns `None` when neither is available.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let album = deezer.album(302127).await?.unwrap();
+    /// if let Some(album) = album.resolve_available(&deezer).await? {
+    ///     assert!(album.available);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn resolve_available(&self, client: &DeezerClient) -> Result<Option<Album>> {
+        if self.available {
+            return Ok(Some(self.clone()));
+        }
+        let alternative = match &self.alternative_album {
+            Some(alternative) => client.album(alternative.id).await?,
+            None => None,
+        };
+
+        Ok(alternative.filter(|album| album.available))
+    }
 
-    /// `The url of the artist picture in size xl`
-    pub picture_xl: String,
-}
+    /// Fetches the genres of the album, without fetching the whole album again.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let mut album = deezer.album(302127).await?.unwrap();
+    /// album.genres = album.fetch_genres(&deezer).await?.into();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_genres(&self, client: &DeezerClient) -> Result<Vec<AlbumGenre>> {
+        client.album_genres(self.id).await
+    }
 
-impl AlbumArtist {
-    /// Returns the corresponding [`Artist`].
+    /// Returns the tracks which are readable for the current user.
+    ///
+    /// No tracks are readable when the album isn't [`available`](Album::available),
+    /// tracks without a [`readable`](PartialTrack::readable) flag are considered readable.
     ///
-    /// # Panics
+    /// # Examples
+    /// ```rust
+    /// # use deezer::models::{Album, AlbumTrack};
+    /// let mut album = Album::new(302127, "Discovery");
+    /// album.available = true;
+    /// let mut unreadable = AlbumTrack::new(3135553, "One More Time");
+    /// unreadable.readable = Some(false);
+    /// album.tracks = vec![unreadable, AlbumTrack::new(3135554, "Aerodynamic")].into();
+    ///
+    /// let readable: Vec<_> = album.readable_tracks().map(|track| track.id.get()).collect();
+    /// assert_eq!(readable, vec![3135554]);
+    /// ```
+    pub fn readable_tracks(&self) -> impl Iterator<Item = &AlbumTrack> {
+        let available = self.available;
+        self.tracks
+            .iter()
+            .filter(move |track| available && track.readable != Some(false))
+    }
+
+    /// Fetches all tracks of the album from its [`tracklist_api_url`](Album::tracklist_api_url).
     ///
-    /// Can panic when the [artist api](https://developers.deezer.com/api/artist) returns `404 - Not Found`.
+    /// Unlike [`tracks`](Album::tracks) this follows the pagination of the api,
+    /// so albums with many tracks are returned completely.
     ///
-    /// This should never happen as [`AlbumArtist`] references an existing [`Artist`].
-    pub async fn get_full(&self) -> Result<Artist> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let artist = Artist::get(self.id).await?.unwrap();
-        Ok(artist)
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let album = deezer.album(302127).await?.unwrap();
+    /// let tracks = album.fetch_tracklist(&deezer).await?;
+    /// assert_eq!(tracks.len() as u64, album.nb_tracks);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_tracklist(&self, client: &DeezerClient) -> Result<Vec<AlbumTrack>> {
+        client.get_all_pages(&self.tracklist_api_url).await
     }
 }
 
-/// Subset of [`Artist`].
+/// Subset of [`Album`].
 ///
-/// Use [`get_full()`] for the full [`Artist`].
+/// Embedded in other api objects, which fields are available depends on the embedding api.
 ///
-/// [`get_full()`]: AlbumTrackArtist::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct AlbumTrackArtist {
-    /// `Artist's Deezer Id`
-    pub id: u64,
+/// Use [`get_full()`] for the full [`Album`].
+///
+/// [`get_full()`]: PartialAlbum::get_full
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default, DeezerPartial)]
+#[deezer(full = Album)]
+#[non_exhaustive]
+pub struct PartialAlbum {
+    /// `The Deezer album id`
+    pub id: AlbumId,
 
-    /// `Artist's name`
-    pub name: String,
+    /// `The album title`
+    pub title: String,
+
+    /// `The url of the album on Deezer`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+
+    /// `The url of the album's cover.`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover: Option<String>,
+
+    /// `The url of the album's cover in size small.`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover_small: Option<String>,
+
+    /// `The url of the album's cover in size medium.`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover_medium: Option<String>,
+
+    /// `The url of the album's cover in size big.`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover_big: Option<String>,
+
+    /// `The url of the album's cover in size xl.`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover_xl: Option<String>,
+
+    /// `The md5 hash of the album's cover image`
+    #[serde(default, deserialize_with = "crate::serde_helpers::empty_string_as_none", skip_serializing_if = "Option::is_none")]
+    pub md5_image: Option<String>,
+
+    /// `The album's first genre id (You should use the genre list instead).`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genre_id: Option<i32>,
+
+    /// `The number of album's Fans`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fans: Option<u64>,
+
+    /// `The album's release date`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_date: Option<String>,
+
+    /// `The record type of the album (EP / ALBUM / etc..)`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub record_type: Option<RecordType>,
 
-    /// `Artist's Deezer tracklist`
-    pub tracklist: String,
+    /// `Whether the album contains explicit lyrics`
+    #[serde(rename = "explicit_lyrics", default, skip_serializing_if = "Option::is_none")]
+    pub has_explicit_lyrics: Option<bool>,
+
+    /// `The position of the album in the charts`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<u64>,
+
+    /// `The time when the album has been added to the user's favorites`
+    #[serde(rename = "time_add", default, skip_serializing_if = "Option::is_none")]
+    pub added_on: Option<u64>,
+
+    /// `The artist this album belongs to`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artist: Option<PartialArtist>,
 }
 
-impl AlbumTrackArtist {
-    /// Returns the corresponding [`Artist`].
+impl PartialAlbum {
+    /// Creates an album with the given id and title, all other fields are left empty.
+    pub fn new(id: impl Into<AlbumId>, title: impl Into<String>) -> Self {
+        PartialAlbum {
+            id: id.into(),
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the url of the cover in the given size.
     ///
-    /// # Panics
+    /// Returns `None` when the embedding api doesn't provide this size.
+    pub fn image_url(&self, size: ImageSize) -> Option<&str> {
+        match size {
+            ImageSize::Small => self.cover_small.as_deref(),
+            ImageSize::Medium => self.cover_medium.as_deref(),
+            ImageSize::Big => self.cover_big.as_deref(),
+            ImageSize::Xl => self.cover_xl.as_deref(),
+        }
+    }
+
+    /// Returns the url of the cover in a custom size of `pixels`x`pixels`, see [`Album::cover_url()`].
+    ///
+    /// Returns `None` without an [`md5_image`](PartialAlbum::md5_image).
+    pub fn cover_url(&self, pixels: u32) -> Option<String> {
+        self.md5_image
+            .as_deref()
+            .map(|md5| cdn_image_url("cover", md5, pixels))
+    }
+
+    /// Returns the parsed [`release_date`](PartialAlbum::release_date).
     ///
-    /// Can panic when the [artist api](https://developers.deezer.com/api/artist) returns `404 - Not Found`.
+    /// Returns `None` when the release date is unknown.
     ///
-    /// This should never happen as [`AlbumTrackArtist`] references an existing [`Artist`].
-    pub async fn get_full(&self) -> Result<Artist> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let artist = Artist::get(self.id).await?.unwrap();
-        Ok(artist)
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use deezer::models::PartialAlbum;
+    /// # use chrono::NaiveDate;
+    /// let album: PartialAlbum = serde_json::from_str(r#"{
+    ///     "id": 302127,
+    ///     "title": "Discovery",
+    ///     "link": "https://www.deezer.com/album/302127",
+    ///     "cover": "https://api.deezer.com/album/302127/image",
+    ///     "genre_id": 113,
+    ///     "fans": 10,
+    ///     "release_date": "2001-03-07",
+    ///     "record_type": "album",
+    ///     "explicit_lyrics": false
+    /// }"#).unwrap();
+    ///
+    /// assert_eq!(album.released_on(), NaiveDate::from_ymd_opt(2001, 3, 7));
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn released_on(&self) -> Option<NaiveDate> {
+        self.release_date.as_deref().and_then(crate::dates::parse_date)
+    }
+
+    /// Returns the time when the album has been added to the user's favorites.
+    #[cfg(feature = "chrono")]
+    pub fn added_at(&self) -> Option<DateTime<Utc>> {
+        self.added_on.and_then(crate::dates::from_timestamp)
     }
 }
 
-/// Subset of [`Track`].
+/// The record type of an [`Album`].
 ///
-/// Use [`get_full()`] for the full [`Track`].
+/// Record types unknown to this crate are kept as [`RecordType::Unknown`].
 ///
-/// [`get_full()`]: AlbumTrack::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct AlbumTrack {
-    /// `The track's Deezer id`
-    pub id: u64,
-
-    /// `True if the track is readable in the player for the current user`
-    pub readable: bool,
+/// # Examples
+///
+/// ```rust
+/// # use deezer::models::RecordType;
+/// assert_eq!(RecordType::from("ep".to_string()), RecordType::Ep);
+/// assert_eq!(RecordType::from("compile".to_string()), RecordType::Compilation);
+/// assert_eq!(RecordType::Single.as_str(), "single");
+/// ```
+///
+/// Values of unexpected types are kept as well:
+///
+/// ```rust
+/// # use deezer::models::RecordType;
+/// let record_types: Vec<RecordType> = serde_json::from_str(r#"["ep", "mixtape", 3, null]"#).unwrap();
+///
+/// assert_eq!(record_types, vec![
+///     RecordType::Ep,
+///     RecordType::Unknown("mixtape".to_string()),
+///     RecordType::Unknown("3".to_string()),
+///     RecordType::Unknown(String::new()),
+/// ]);
+/// ```
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(into = "String")]
+pub enum RecordType {
+    /// A full length album
+    Album,
 
-    /// `The track's full title`
-    pub title: String,
+    /// An extended play
+    Ep,
 
-    /// `The track's short title`
-    pub title_short: String,
+    /// A single
+    Single,
 
-    /// `The track version`
-    pub title_version: String,
+    /// A compilation
+    Compilation,
 
-    /// `The url of the track on Deezer`
-    pub link: String,
+    /// A record type not known to this crate
+    Unknown(String),
+}
 
-    /// `The track's duration in seconds`
-    #[serde(rename = "duration")]
-    pub duration_in_seconds: u64,
+impl RecordType {
+    /// The position of the record type in a discography.
+    pub(crate) fn rank(&self) -> u8 {
+        match self {
+            RecordType::Album => 0,
+            RecordType::Ep => 1,
+            RecordType::Single => 2,
+            RecordType::Compilation => 3,
+            RecordType::Unknown(_) => 4,
+        }
+    }
 
-    /// `The track's Deezer rank`
-    pub rank: u64,
+    /// Returns the record type as used by the api.
+    pub fn as_str(&self) -> &str {
+        match self {
+            RecordType::Album => "album",
+            RecordType::Ep => "ep",
+            RecordType::Single => "single",
+            RecordType::Compilation => "compile",
+            RecordType::Unknown(record_type) => record_type,
+        }
+    }
+}
 
-    /// `Whether the track contains explicit lyrics`
-    pub explicit_lyrics: bool,
+impl From<String> for RecordType {
+    fn from(record_type: String) -> Self {
+        match record_type.to_lowercase().as_str() {
+            "album" => RecordType::Album,
+            "ep" => RecordType::Ep,
+            "single" => RecordType::Single,
+            "compile" | "compilation" => RecordType::Compilation,
+            _ => RecordType::Unknown(record_type),
+        }
+    }
+}
 
-    /// `The url of track's preview file. This file contains the first 30 seconds of the track`
-    pub preview: String,
+impl<'de> Deserialize<'de> for RecordType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        crate::serde_helpers::lenient_string(deserializer).map(RecordType::from)
+    }
+}
 
-    /// `AlbumTrackArtist object`
-    pub artist: AlbumTrackArtist,
+impl From<RecordType> for String {
+    fn from(record_type: RecordType) -> Self {
+        match record_type {
+            RecordType::Unknown(record_type) => record_type,
+            record_type => record_type.as_str().to_string(),
+        }
+    }
 }
 
-impl AlbumTrack {
-    /// Returns the corresponding [`Track`].
-    ///
-    /// # Panics
-    ///
-    /// Can panic when the [track api](https://developers.deezer.com/api/track) returns `404 - Not Found`.
-    ///
-    /// This should never happen as [`AlbumTrack`] references an existing [`Track`].
-    pub async fn get_full(&self) -> Result<Track> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let track = Track::get(self.id).await?.unwrap();
-        Ok(track)
+impl fmt::Display for RecordType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
     }
 }
 
+/// Artist of an [`Album`], see [`PartialArtist`].
+pub type AlbumArtist = PartialArtist;
+
+/// Artist of an [`AlbumTrack`], see [`PartialArtist`].
+pub type AlbumTrackArtist = PartialArtist;
+
+/// Track of an [`Album`], see [`PartialTrack`].
+pub type AlbumTrack = PartialTrack;
+
 /// Subset of [`Genre`].
 ///
 /// Use [`get_full()`] for the full [`Genre`].
 ///
 /// [`get_full()`]: AlbumGenre::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default, DeezerPartial)]
+#[deezer(full = Genre)]
+#[non_exhaustive]
 pub struct AlbumGenre {
     /// `The Genre's id`
-    pub id: u64,
+    pub id: GenreId,
 
     /// `The Genre's name`
     pub name: String,
@@ -291,16 +611,12 @@ pub struct AlbumGenre {
 }
 
 impl AlbumGenre {
-    /// Returns the corresponding [`Genre`].
-    ///
-    /// # Panics
-    ///
-    /// Can panic when the [genre api](https://developers.deezer.com/api/genre) returns `404 - Not Found`.
-    ///
-    /// This should never happen as [`AlbumGenre`] references an existing [`Genre`].
-    pub async fn get_full(&self) -> Result<Genre> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let genre = Genre::get(self.id).await?.unwrap();
-        Ok(genre)
+    /// Creates a genre with the given id and name, all other fields are left empty.
+    pub fn new(id: impl Into<GenreId>, name: impl Into<String>) -> Self {
+        AlbumGenre {
+            id: id.into(),
+            name: name.into(),
+            ..Default::default()
+        }
     }
 }
@@ -1,10 +1,14 @@
 //! [Artist API](https://developers.deezer.com/api/artist)
 #![warn(missing_docs)]
 
-use serde::{Deserialize, Serialize};
+use std::fmt;
 
-use crate::models::{Album, DeezerObject};
-use crate::Result;
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::models::{ArtistId, DeezerObject, DeezerPartial, ImageSize, PartialAlbum, PartialTrack, RecordType};
+use crate::{DeezerClient, Result};
 
 /// Contains all the information provided for an Artist.
 ///
@@ -41,10 +45,11 @@ use crate::Result;
 /// # }
 ///
 /// ```
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct Artist {
     /// `The artist's Deezer id`
-    pub id: u64,
+    pub id: ArtistId,
 
     /// `The artist's name`
     pub name: String,
@@ -85,21 +90,99 @@ pub struct Artist {
     pub tracklist: String,
 }
 
+impl Artist {
+    /// Creates an artist with the given id and name, all other fields are left empty.
+    pub fn new(id: impl Into<ArtistId>, name: impl Into<String>) -> Self {
+        Artist {
+            id: id.into(),
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Fetches the artist's top tracks from its [`tracklist`](Artist::tracklist).
+    ///
+    /// `limit` overrides the number of tracks the api returns by default.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let artist = deezer.artist(27).await?.unwrap();
+    /// let tracks = artist.fetch_top_tracks(&deezer, Some(10)).await?;
+    /// assert!(tracks.len() <= 10);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_top_tracks(&self, client: &DeezerClient, limit: Option<u32>) -> Result<Vec<PartialTrack>> {
+        match limit {
+            // the tracklist already contains a default limit, which would collide with ours
+            Some(_) => {
+                let url = self.tracklist.split('?').next().unwrap_or_default();
+                client.get_list_from_url(url, limit, None).await
+            }
+            None => client.get_list_from_url(&self.tracklist, None, None).await,
+        }
+    }
+
+    /// Fetches all albums of the artist, see [`DeezerClient::artist_discography()`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let artist = deezer.artist(27).await?.unwrap();
+    /// for group in artist.fetch_discography(&deezer).await?.groups {
+    ///     println!("{:?} {:?}: {} releases", group.year, group.record_type, group.albums.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_discography(&self, client: &DeezerClient) -> Result<Discography> {
+        client.artist_discography(self.id).await
+    }
+
+    /// Returns the url of the picture in the given size.
+    pub fn image_url(&self, size: ImageSize) -> &str {
+        match size {
+            ImageSize::Small => &self.picture_small,
+            ImageSize::Medium => &self.picture_medium,
+            ImageSize::Big => &self.picture_big,
+            ImageSize::Xl => &self.picture_xl,
+        }
+    }
+}
+
 impl DeezerObject for Artist {
-    fn get_api_url(id: u64) -> String {
+    type Id = ArtistId;
+
+    fn get_api_url(id: ArtistId) -> String {
         format!("artist/{}", id)
     }
 }
 
+/// Formats the artist as its name.
+impl fmt::Display for Artist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
 /// Subset of [`Artist`].
 ///
 /// Use [`get_full()`] for the corresponding [`Artist`] struct.
 ///
 /// [`get_full()`]: ContributorArtist::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default, DeezerPartial)]
+#[deezer(full = Artist)]
+#[non_exhaustive]
 pub struct ContributorArtist {
     /// `The artist's Deezer id`
-    pub id: u64,
+    pub id: ArtistId,
 
     /// `The artist's name`
     pub name: String,
@@ -129,78 +212,276 @@ pub struct ContributorArtist {
 
     /// `API Link to the top of this artist`
     pub tracklist: String,
+
+    /// `The artist's role on the track or album`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<ContributorRole>,
 }
 
 impl ContributorArtist {
-    /// Returns the corresponding [`Artist`].
-    pub async fn get_full(&self) -> Result<Artist> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let artist = Artist::get(self.id).await?.unwrap();
-        Ok(artist)
+    /// Creates an artist with the given id and name, all other fields are left empty.
+    pub fn new(id: impl Into<ArtistId>, name: impl Into<String>) -> Self {
+        ContributorArtist {
+            id: id.into(),
+            name: name.into(),
+            ..Default::default()
+        }
     }
 }
 
-/// Artist's album - Subset of [`Album`].
+/// The role of a [`ContributorArtist`] on a track or album.
 ///
-/// [Reference](https://developers.deezer.com/api/artist/albums)
+/// Roles are ordered by their importance for credits, main artists first.
 ///
+/// # Examples
 ///
-/// Use [`get_full()`] for the corresponding [`Album`] struct.
+/// ```rust
+/// # use deezer::models::ContributorRole;
+/// let mut roles = vec![
+///     ContributorRole::from("Featured".to_string()),
+///     ContributorRole::from("Main".to_string()),
+/// ];
+/// roles.sort();
 ///
-/// [`get_full()`]: ArtistAlbum::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct ArtistAlbum {
-    /// `The Deezer album id`
-    pub id: u64,
+/// assert_eq!(roles, vec![ContributorRole::Main, ContributorRole::Featured]);
+/// ```
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(into = "String")]
+pub enum ContributorRole {
+    /// The main artist
+    Main,
 
-    /// `The album title`
-    pub title: String,
+    /// A featured artist
+    Featured,
 
-    /// `The url of the album on Deezer`
-    pub link: String,
+    /// A role not known to this crate
+    Unknown(String),
+}
 
-    /// `The url of the album's cover.`
-    pub cover: String,
+impl ContributorRole {
+    /// Returns the role as used by the api.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ContributorRole::Main => "Main",
+            ContributorRole::Featured => "Featured",
+            ContributorRole::Unknown(role) => role,
+        }
+    }
+}
 
-    /// `The url of the album's cover in size small.`
-    pub cover_small: String,
+impl From<String> for ContributorRole {
+    fn from(role: String) -> Self {
+        match role.to_lowercase().as_str() {
+            "main" => ContributorRole::Main,
+            "featured" => ContributorRole::Featured,
+            _ => ContributorRole::Unknown(role),
+        }
+    }
+}
 
-    /// `The url of the album's cover in size medium.`
-    pub cover_medium: String,
+/// Values of unexpected types, like numbers, are kept as [`ContributorRole::Unknown`].
+impl<'de> Deserialize<'de> for ContributorRole {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        crate::serde_helpers::lenient_string(deserializer).map(ContributorRole::from)
+    }
+}
 
-    /// `The url of the album's cover in size big.`
-    pub cover_big: String,
+impl From<ContributorRole> for String {
+    fn from(role: ContributorRole) -> Self {
+        match role {
+            ContributorRole::Unknown(role) => role,
+            role => role.as_str().to_string(),
+        }
+    }
+}
 
-    /// `The url of the album's cover in size xl.`
-    pub cover_xl: String,
+impl fmt::Display for ContributorRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
-    /// `The album's first genre id (You should use the genre list instead).`
-    pub genre_id: Option<i32>,
+/// Subset of [`Artist`].
+///
+/// Embedded in other api objects, which fields are available depends on the embedding api.
+///
+/// Use [`get_full()`] for the corresponding [`Artist`] struct.
+///
+/// [`get_full()`]: PartialArtist::get_full
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default, DeezerPartial)]
+#[deezer(full = Artist)]
+#[non_exhaustive]
+pub struct PartialArtist {
+    /// `The artist's Deezer id`
+    pub id: ArtistId,
 
-    /// `The number of album's Fans`
-    pub fans: u32,
-    /// `The album's release date`
+    /// `The artist's name`
+    pub name: String,
 
-    pub release_date: String,
-    /// `The record type of the album (EP / ALBUM / etc..)`
+    /// `The url of the artist on Deezer`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
 
-    pub record_type: String,
+    /// `The url of the artist picture`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub picture: Option<String>,
 
-    /// `Whether the album contains explicit lyrics`
-    pub explicit_lyrics: bool,
+    /// `The url of the artist picture in size small`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub picture_small: Option<String>,
+
+    /// `The url of the artist picture in size medium`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub picture_medium: Option<String>,
+
+    /// `The url of the artist picture in size big`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub picture_big: Option<String>,
+
+    /// `The url of the artist picture in size xl`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub picture_xl: Option<String>,
+
+    /// `True if the artist has a smartradio`
+    #[serde(rename = "radio", default, skip_serializing_if = "Option::is_none")]
+    pub has_radio: Option<bool>,
+
+    /// `API Link to the top of this artist`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracklist: Option<String>,
+
+    /// `The position of the artist in the charts`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<u64>,
+
+    /// `The time when the artist has been added to the user's favorites`
+    #[serde(rename = "time_add", default, skip_serializing_if = "Option::is_none")]
+    pub added_on: Option<u64>,
 }
 
-impl DeezerObject for ArtistAlbum {
-    fn get_api_url(id: u64) -> String {
-        format!("artist/{}/albums", id)
+impl PartialArtist {
+    /// Creates an artist with the given id and name, all other fields are left empty.
+    pub fn new(id: impl Into<ArtistId>, name: impl Into<String>) -> Self {
+        PartialArtist {
+            id: id.into(),
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the url of the picture in the given size.
+    ///
+    /// Returns `None` when the embedding api doesn't provide this size.
+    pub fn image_url(&self, size: ImageSize) -> Option<&str> {
+        match size {
+            ImageSize::Small => self.picture_small.as_deref(),
+            ImageSize::Medium => self.picture_medium.as_deref(),
+            ImageSize::Big => self.picture_big.as_deref(),
+            ImageSize::Xl => self.picture_xl.as_deref(),
+        }
+    }
+
+    /// Returns the time when the artist has been added to the user's favorites.
+    #[cfg(feature = "chrono")]
+    pub fn added_at(&self) -> Option<DateTime<Utc>> {
+        self.added_on.and_then(crate::dates::from_timestamp)
     }
 }
 
-impl ArtistAlbum {
-    /// Returns the corresponding [`Album`].
-    pub async fn get_full(&self) -> Result<Album> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let artist = Album::get(self.id).await?.unwrap();
-        Ok(artist)
+/// Album of an [`Artist`], see [`PartialAlbum`].
+///
+/// [Reference](https://developers.deezer.com/api/artist/albums)
+pub type ArtistAlbum = PartialAlbum;
+
+
+/// All albums of an artist, newest first.
+///
+/// The albums are grouped by their release year and [`RecordType`], within a group they are sorted
+/// by release date. Albums without a known release date or record type are listed last.
+///
+/// # Examples
+/// ```rust
+/// # use deezer::models::{ArtistAlbum, Discography, RecordType};
+/// let mut single = ArtistAlbum::new(1, "Single");
+/// single.release_date = Some("2013-04-19".to_string());
+/// single.record_type = Some(RecordType::Single);
+/// let mut album = ArtistAlbum::new(2, "Album");
+/// album.release_date = Some("2013-05-17".to_string());
+/// album.record_type = Some(RecordType::Album);
+/// let mut unknown = ArtistAlbum::new(3, "Unknown");
+/// unknown.release_date = Some("2013-01-01".to_string());
+///
+/// let discography = Discography::new(vec![single, unknown, album]);
+///
+/// assert_eq!(discography.groups[0].year, Some(2013));
+/// assert_eq!(discography.groups[0].record_type, Some(RecordType::Album));
+/// assert_eq!(discography.groups[1].record_type, Some(RecordType::Single));
+/// assert_eq!(discography.groups[2].record_type, None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct Discography {
+    /// The albums grouped by release year and record type
+    pub groups: Vec<DiscographyGroup>,
+}
+
+/// Albums of a [`Discography`] released in the same year with the same record type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DiscographyGroup {
+    /// The release year, `None` when unknown
+    pub year: Option<u16>,
+
+    /// The record type of the albums, `None` when unknown
+    pub record_type: Option<RecordType>,
+
+    /// The albums, newest first
+    pub albums: Vec<ArtistAlbum>,
+}
+
+impl Discography {
+    /// Sorts and groups the given albums.
+    pub fn new(mut albums: Vec<ArtistAlbum>) -> Self {
+        albums.sort_by(|a, b| {
+            release_year(b)
+                .cmp(&release_year(a))
+                .then_with(|| record_type_rank(a).cmp(&record_type_rank(b)))
+                .then_with(|| b.release_date.cmp(&a.release_date))
+        });
+        let mut groups: Vec<DiscographyGroup> = Vec::new();
+        for album in albums {
+            let year = release_year(&album);
+            let record_type = album.record_type.clone();
+            match groups.last_mut() {
+                Some(group) if group.year == year && group.record_type == record_type => group.albums.push(album),
+                _ => groups.push(DiscographyGroup {
+                    year,
+                    record_type,
+                    albums: vec![album],
+                }),
+            }
+        }
+
+        Discography { groups }
+    }
+
+    /// Returns all albums in the order of the groups.
+    pub fn albums(&self) -> impl Iterator<Item = &ArtistAlbum> {
+        self.groups.iter().flat_map(|group| group.albums.iter())
     }
-}
\ No newline at end of file
+}
+
+/// The year of the `YYYY-MM-DD` release date, deezer uses `0000-00-00` for unknown dates.
+fn release_year(album: &ArtistAlbum) -> Option<u16> {
+    album
+        .release_date
+        .as_deref()
+        .and_then(|date| date.get(..4))
+        .and_then(|year| year.parse().ok())
+        .filter(|year| *year > 0)
+}
+
+/// Albums without a record type are sorted after all known record types.
+fn record_type_rank(album: &ArtistAlbum) -> u8 {
+    album.record_type.as_ref().map_or(u8::MAX, RecordType::rank)
+}
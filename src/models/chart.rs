@@ -1,7 +1,8 @@
 //! [Chart API](https://developers.deezer.com/api/chart)
 #![warn(missing_docs)]
-use crate::models::{Album, Artist, DeezerArray, DeezerObject, Playlist, PlaylistUser, Track};
-use crate::Result;
+use crate::fetch::BatchResult;
+use crate::models::{Album, AlbumId, AnyId, Artist, ArtistId, DeezerArray, DeezerPartial, PartialAlbum, PartialArtist, PartialTrack, Playlist, PlaylistId, PlaylistUser, Track, TrackId};
+use crate::{DeezerClient, DeezerError, Result};
 use serde::{Deserialize, Serialize};
 
 /// Charts of a specified genre
@@ -19,7 +20,8 @@ use serde::{Deserialize, Serialize};
 /// # }
 ///
 /// ```
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct Chart {
     /// Vector of ChartTrack objects in the Chart
     pub tracks: DeezerArray<ChartTrack>,
@@ -34,333 +36,147 @@ pub struct Chart {
     pub playlists: DeezerArray<ChartPlaylist>,
 }
 
-/// Subset of [`Track`].
-///
-/// Use [`get_full()`] for the full [`Track`].
-///
-/// [`get_full()`]: ChartTrack::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct ChartTrack {
-    /// `The track's Deezer id`
-    pub id: u64,
-
-    /// `The track's full title`
-    pub title: String,
-
-    /// `The track's short title`
-    pub title_short: String,
-
-    /// `The track's version`
-    pub title_version: String,
-
-    /// `The url of the track on Deezer`
-    pub link: String,
-
-    /// `The track's duration in seconds`
-    #[serde(rename = "duration")]
-    pub duration_in_seconds: u64,
-
-    /// `The track's Deezer rank`
-    pub rank: u64,
-
-    /// `Whether the track contains explicit lyrics`
-    #[serde(rename = "explicit_lyrics")]
-    pub has_explicit_lyrics: bool,
-
-    /// `The url of track's preview file. This file contains the first 30 seconds of the track`
-    #[serde(default)]
-    pub preview_url: Option<String>,
-
-    /// `The position of the track in the charts`
-    pub position: u64,
-
-    /// `Returns an ChartTrackArtist object of the artist this track belongs to`
-    pub artist: ChartTrackArtist,
+/// Maximum number of requests [`Chart::hydrate()`] keeps in flight per kind of entry.
+const HYDRATION_CONCURRENCY: usize = 8;
 
-    /// `Returns an ChartTrackAlbum object of the album this track belongs to`
-    pub album: ChartTrackAlbum,
-}
-
-impl ChartTrack {
-    /// Returns the corresponding [`Track`].
+impl Chart {
+    /// Resolves all tracks, albums and artists of the chart to their full objects.
     ///
-    /// # Panics
+    /// Tracks, albums and artists are resolved at the same time, with up to 8 requests in flight for each of them.
+    /// Entries which fail to resolve are reported in the [`BatchResult::failed`] of their kind.
     ///
-    /// Can panic when the [track api](https://developers.deezer.com/api/track) returns `404 - Not Found`.
-    ///
-    /// This should never happen as [`ChartTrack`] references an existing [`Track`].
-    pub async fn get_full(&self) -> Result<Track> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let track = Track::get(self.id).await?.unwrap();
-        Ok(track)
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let charts = deezer.charts().await?;
+    /// let hydrated = charts.hydrate(&deezer).await;
+    /// for (id, err) in hydrated.failed() {
+    ///     println!("{} could not be resolved: {}", id, err);
+    /// }
+    /// let charts = hydrated.into_chart();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn hydrate(&self, client: &DeezerClient) -> HydratedChart {
+        let (tracks, albums, artists) = futures::join!(
+            self.tracks.get_all_full(client, HYDRATION_CONCURRENCY),
+            self.albums.get_all_full(client, HYDRATION_CONCURRENCY),
+            self.artists.get_all_full(client, HYDRATION_CONCURRENCY),
+        );
+
+        HydratedChart {
+            tracks,
+            albums,
+            artists,
+            playlists: self.playlists.clone(),
+        }
     }
 }
 
-/// Subset of [`Artist`].
-///
-/// Use [`get_full()`] for the full [`Artist`].
-///
-/// [`get_full()`]: ChartTrackArtist::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct ChartTrackArtist {
-    /// `The artist's Deezer id`
-    pub id: u64,
-
-    /// `The artist's name`
-    pub name: String,
+/// Result of [`Chart::hydrate()`], the resolved entries of a [`Chart`] and the ones which failed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct HydratedChart {
+    /// The resolved tracks, ordered by chart position
+    pub tracks: BatchResult<Track, TrackId>,
 
-    /// `The url of the artist on Deezer`
-    pub link: String,
+    /// The resolved albums, ordered by chart position
+    pub albums: BatchResult<Album, AlbumId>,
 
-    /// `The url of the artist picture`
-    pub picture: String,
+    /// The resolved artists, ordered by chart position
+    pub artists: BatchResult<Artist, ArtistId>,
 
-    /// `The url of the artist picture in size small`
-    pub picture_small: String,
-
-    /// `The url of the artist picture in size medium`
-    pub picture_medium: String,
-
-    /// `The url of the artist picture in size big`
-    pub picture_big: String,
-
-    /// `The url of the artist picture in size xl`
-    pub picture_xl: String,
-
-    /// `True if the artist has a smartradio`
-    #[serde(rename = "radio")]
-    pub has_radio: bool,
+    /// The playlists of the chart, which aren't resolved
+    pub playlists: DeezerArray<ChartPlaylist>,
 }
 
-impl ChartTrackArtist {
-    /// Returns the corresponding [`Artist`].
-    ///
-    /// # Panics
-    ///
-    /// Can panic when the [artist api](https://developers.deezer.com/api/artist) returns `404 - Not Found`.
-    ///
-    /// This should never happen as [`ChartTrackArtist`] references an existing [`Artist`].
-    pub async fn get_full(&self) -> Result<Artist> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let artist = Artist::get(self.id).await?.unwrap();
-        Ok(artist)
+impl HydratedChart {
+    /// Returns whether all entries were resolved.
+    pub fn is_complete(&self) -> bool {
+        self.tracks.is_complete() && self.albums.is_complete() && self.artists.is_complete()
     }
-}
-
-/// Subset of [`Album`].
-///
-/// Use [`get_full()`] for the full [`Album`].
-///
-/// [`get_full()`]: ChartTrackAlbum::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct ChartTrackAlbum {
-    /// `The Deezer album id`
-    pub id: u64,
-
-    /// `The album title`
-    pub title: String,
-
-    /// `The url of the album's cover.`
-    pub cover: String,
-
-    /// `The url of the album's cover in size small.`
-    pub cover_small: String,
-
-    /// `The url of the album's cover in size medium.`
-    pub cover_medium: String,
-
-    /// `The url of the album's cover in size big.`
-    pub cover_big: String,
 
-    /// `The url of the album's cover in size xl.`
-    pub cover_xl: String,
-}
+    /// Returns the entries which failed to resolve together with their error.
+    pub fn failed(&self) -> impl Iterator<Item = (AnyId, &DeezerError)> {
+        let tracks = self.tracks.failed.iter().map(|(id, err)| (AnyId::from(*id), err));
+        let albums = self.albums.failed.iter().map(|(id, err)| (AnyId::from(*id), err));
+        let artists = self.artists.failed.iter().map(|(id, err)| (AnyId::from(*id), err));
 
-impl ChartTrackAlbum {
-    /// Returns the corresponding [`Album`].
-    ///
-    /// # Panics
-    ///
-    /// Can panic when the [album api](https://developers.deezer.com/api/album) returns `404 - Not Found`.
-    ///
-    /// This should never happen as [`ChartTrackAlbum`] references an existing [`Album`].
-    pub async fn get_full(&self) -> Result<Album> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let album = Album::get(self.id).await?.unwrap();
-        Ok(album)
+        tracks.chain(albums).chain(artists)
     }
-}
-
-/// Subset of [`Album`].
-///
-/// Use [`get_full()`] for the full [`Album`].
-///
-/// [`get_full()`]: ChartAlbum::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct ChartAlbum {
-    /// `The Deezer album id`
-    pub id: u64,
-
-    /// `The album title`
-    pub title: String,
-
-    /// `The url of the album's cover.`
-    pub cover: String,
-
-    /// `The url of the album's cover in size small.`
-    pub cover_small: String,
-
-    /// `The url of the album's cover in size medium.`
-    pub cover_medium: String,
-
-    /// `The url of the album's cover in size big.`
-    pub cover_big: String,
-
-    /// `The url of the album's cover in size xl.`
-    pub cover_xl: String,
 
-    /// `The record type of the album (EP / ALBUM / etc..)`
-    pub record_type: String,
-
-    /// `Whether the album contains explicit lyrics`
-    #[serde(rename = "explicit_lyrics")]
-    pub has_explicit_lyrics: bool,
-
-    /// `The position of the album in the charts`
-    pub position: u64,
-
-    /// `Returns an ChartAlbumArtist object of the artist this album belongs to`
-    pub artist: ChartAlbumArtist,
-}
-
-impl ChartAlbum {
-    /// Returns the corresponding [`Album`].
-    ///
-    /// # Panics
-    ///
-    /// Can panic when the [album api](https://developers.deezer.com/api/album) returns `404 - Not Found`.
-    ///
-    /// This should never happen as [`ChartAlbum`] references an existing [`Album`].
-    pub async fn get_full(&self) -> Result<Album> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let album = Album::get(self.id).await?.unwrap();
-        Ok(album)
+    /// Returns the chart of the resolved entries, leaving out the failed ones.
+    pub fn into_chart(self) -> FullChart {
+        FullChart {
+            tracks: self.tracks.succeeded,
+            albums: self.albums.succeeded,
+            artists: self.artists.succeeded,
+            playlists: self.playlists,
+        }
     }
-}
-
-/// Subset of [`Artist`].
-///
-/// Use [`get_full()`] for the full [`Artist`].
-///
-/// [`get_full()`]: ChartAlbumArtist::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct ChartAlbumArtist {
-    /// `The artist's Deezer id`
-    pub id: u64,
-
-    /// `The artist's name`
-    pub name: String,
-
-    /// `The url of the artist on Deezer`
-    pub link: String,
 
-    /// `The url of the artist picture.`
-    pub picture: String,
-
-    /// `The url of the artist picture in size small`
-    pub picture_small: String,
-
-    /// `The url of the artist picture in size medium`
-    pub picture_medium: String,
-
-    /// `The url of the artist picture in size big`
-    pub picture_big: String,
-
-    /// `The url of the artist picture in size xl`
-    pub picture_xl: String,
-
-    /// `True if the artist has a smartradio`
-    #[serde(rename = "radio")]
-    pub has_radio: bool,
-}
-
-impl ChartAlbumArtist {
-    /// Returns the full [`Artist`].
-    ///
-    /// # Panics
-    ///
-    /// Can panic when the [artist api](https://developers.deezer.com/api/artist) returns `404 - Not Found`.
-    ///
-    /// This should never happen as [`ChartAlbumArtist`] references an existing [`Artist`].
-    pub async fn get_full(&self) -> Result<Artist> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let artist = Artist::get(self.id).await?.unwrap();
-        Ok(artist)
+    /// Returns the chart when all entries were resolved, otherwise the error of the first failed entry.
+    pub fn into_result(self) -> Result<FullChart> {
+        Ok(FullChart {
+            tracks: self.tracks.into_result()?,
+            albums: self.albums.into_result()?,
+            artists: self.artists.into_result()?,
+            playlists: self.playlists,
+        })
     }
 }
 
-/// Subset of [`Artist`].
+/// A [`Chart`] with all entries resolved to their full objects.
 ///
-/// Use [`get_full()`] for the full [`Artist`].
-///
-/// [`get_full()`]: ChartArtist::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct ChartArtist {
-    /// `The artist's Deezer id`
-    pub id: u64,
+/// Use [`Chart::hydrate()`] to create one.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+#[non_exhaustive]
+pub struct FullChart {
+    /// Vector of Track objects in the Chart, ordered by chart position
+    pub tracks: Vec<Track>,
 
-    /// `The artist's name`
-    pub name: String,
+    /// Vector of Album objects in the Chart, ordered by chart position
+    pub albums: Vec<Album>,
 
-    /// `The url of the artist on Deezer`
-    pub link: String,
+    /// Vector of Artist objects in the Chart, ordered by chart position
+    pub artists: Vec<Artist>,
 
-    /// `The url of the artist picture.`
-    pub picture: String,
+    /// Vector of Playlist objects in the Chart
+    pub playlists: DeezerArray<ChartPlaylist>,
+}
 
-    /// `The url of the artist picture in size small`
-    pub picture_small: String,
+/// Track in the [`Chart`], see [`PartialTrack`].
+pub type ChartTrack = PartialTrack;
 
-    /// `The url of the artist picture in size medium`
-    pub picture_medium: String,
+/// Artist of a [`ChartTrack`], see [`PartialArtist`].
+pub type ChartTrackArtist = PartialArtist;
 
-    /// `The url of the artist picture in size big`
-    pub picture_big: String,
+/// Album of a [`ChartTrack`], see [`PartialAlbum`].
+pub type ChartTrackAlbum = PartialAlbum;
 
-    /// `The url of the artist picture in size xl`
-    pub picture_xl: String,
+/// Album in the [`Chart`], see [`PartialAlbum`].
+pub type ChartAlbum = PartialAlbum;
 
-    /// `True if the artist has a smartradio`
-    #[serde(rename = "radio")]
-    pub has_radio: bool,
+/// Artist of a [`ChartAlbum`], see [`PartialArtist`].
+pub type ChartAlbumArtist = PartialArtist;
 
-    /// `The position of the artist in the charts`
-    pub position: u64,
-}
-
-impl ChartArtist {
-    /// Returns the full [`Artist`].
-    ///
-    /// # Panics
-    ///
-    /// Can panic when the [artist api](https://developers.deezer.com/api/artist) returns `404 - Not Found`.
-    ///
-    /// This should never happen as [`ChartArtist`] references an existing [`Artist`].
-    pub async fn get_full(&self) -> Result<Artist> {
-        let artist = Artist::get(self.id).await?.unwrap();
-        Ok(artist)
-    }
-}
+/// Artist in the [`Chart`], see [`PartialArtist`].
+pub type ChartArtist = PartialArtist;
 
 /// Subset of [`Playlist`].
 ///
 /// Use [`get_full()`] for the full [`Playlist`].
 ///
 /// [`get_full()`]: ChartPlaylist::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default, DeezerPartial)]
+#[deezer(full = Playlist)]
+#[non_exhaustive]
 pub struct ChartPlaylist {
     /// The playlist's Deezer id
-    pub id: u64,
+    pub id: PlaylistId,
 
     /// The playlist's title
     pub title: String,
@@ -396,16 +212,59 @@ pub struct ChartPlaylist {
 }
 
 impl ChartPlaylist {
-    /// Returns the full [`Playlist`].
-    ///
-    /// # Panics
-    ///
-    /// Can panic when the [playlist api](https://developers.deezer.com/api/playlist) returns `404 - Not Found`.
-    ///
-    /// This should never happen as [`ChartPlaylist`] references an existing [`Playlist`].
-    pub async fn get_full(&self) -> Result<Playlist> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let playlist = Playlist::get(self.id).await?.unwrap();
-        Ok(playlist)
+    /// Creates a playlist with the given id and title, all other fields are left empty.
+    pub fn new(id: impl Into<PlaylistId>, title: impl Into<String>) -> Self {
+        ChartPlaylist {
+            id: id.into(),
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::MockServer;
+
+    fn chart() -> Chart {
+        Chart {
+            tracks: vec![PartialTrack::new(1, "One More Time"), PartialTrack::new(2, "Aerodynamic")].into(),
+            albums: vec![PartialAlbum::new(10, "Discovery")].into(),
+            artists: vec![PartialArtist::new(20, "Daft Punk")].into(),
+            playlists: DeezerArray::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_entries_are_reported_per_kind() {
+        let server = MockServer::start().await;
+        server
+            .respond("/track/1", serde_json::to_string(&Track::new(1, "One More Time")).unwrap())
+            .respond("/track/2", r#"{"error": {"type": "DataException", "message": "no data", "code": 800}}"#)
+            .respond("/album/10", serde_json::to_string(&Album::new(10, "Discovery")).unwrap())
+            .respond("/artist/20", serde_json::to_string(&Artist::new(20, "Daft Punk")).unwrap());
+        let client = DeezerClient::new().with_base_url(server.url());
+
+        let hydrated = chart().hydrate(&client).await;
+
+        assert!(!hydrated.is_complete());
+        let failed: Vec<AnyId> = hydrated.failed().map(|(id, _)| id).collect();
+        assert_eq!(failed, vec![AnyId::Track(TrackId::new(2))]);
+        let chart = hydrated.into_chart();
+        assert_eq!(chart.tracks.iter().map(|track| track.id).collect::<Vec<_>>(), vec![TrackId::new(1)]);
+        assert_eq!(chart.albums.len(), 1);
+        assert_eq!(chart.artists.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn into_result_fails_with_the_first_error() {
+        let server = MockServer::start().await;
+        server.respond("/track/1", r#"{"error": {"type": "DataException", "message": "no data", "code": 800}}"#);
+        let client = DeezerClient::new().with_base_url(server.url());
+
+        let result = chart().hydrate(&client).await.into_result();
+
+        assert!(matches!(result, Err(DeezerError::NotFound(_))));
     }
 }
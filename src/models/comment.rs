@@ -1,7 +1,8 @@
 //! [Comment API](https://developers.deezer.com/api/comment)
 #![warn(missing_docs)]
-use crate::models::{DeezerObject, User};
-use crate::Result;
+use crate::models::{DeezerObject, PartialUser};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Contains all the information provided for a Comment.
@@ -35,9 +36,11 @@ use serde::{Deserialize, Serialize};
 /// # }
 ///
 /// ```
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct Comment {
     /// The comment's Deezer id
+    #[serde(deserialize_with = "crate::serde_helpers::id")]
     pub id: u64,
 
     /// The content of the comment
@@ -54,54 +57,43 @@ pub struct Comment {
     pub author: CommentAuthor,
 }
 
-impl DeezerObject for Comment {
-    fn get_api_url(id: u64) -> String {
-        format!("comment/{}", id)
+impl Comment {
+    /// Creates a comment with the given id and text, all other fields are left empty.
+    pub fn new(id: u64, text: impl Into<String>) -> Self {
+        Comment {
+            id,
+            text: text.into(),
+            ..Default::default()
+        }
     }
-}
-
-/// Subset of [`User`].
-///
-/// Use [`get_full()`] for the corresponding [`User`] struct.
-///
-/// [`get_full()`]: CommentAuthor::get_full
-#[derive(Deserialize, Serialize, Debug)]
-pub struct CommentAuthor {
-    /// The comment's Deezer id
-    pub id: u64,
-
-    /// The user's Deezer nickname
-    pub name: String,
-
-    /// The url of the profil for the user on Deezer
-    pub link: String,
 
-    /// The url of the user's profile picture
-    pub picture: String,
-
-    /// The url of the user's profile picture in size small
-    pub picture_small: String,
-
-    /// The url of the user's profile picture in size medium
-    pub picture_medium: String,
-
-    /// The url of the user's profile picture in size big
-    pub picture_big: String,
-
-    /// The url of the user's profile picture in size xl
-    pub picture_xl: String,
+    /// Returns the time when the comment was posted.
+    ///
+    /// ```rust
+    /// # use deezer::models::Comment;
+    /// let mut comment = Comment::new(4179157801, "Great album!");
+    /// comment.date = 1_500_000_000;
+    ///
+    /// assert_eq!(comment.posted_at().unwrap().to_rfc3339(), "2017-07-14T02:40:00+00:00");
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn posted_at(&self) -> Option<DateTime<Utc>> {
+        crate::dates::from_timestamp(self.date)
+    }
 }
 
-impl CommentAuthor {
-    /// Returns the full [`User`].
-    pub async fn get_full(&self) -> Result<User> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let user = User::get(self.id).await?.unwrap();
-        Ok(user)
+impl DeezerObject for Comment {
+    type Id = u64;
+
+    fn get_api_url(id: u64) -> String {
+        format!("comment/{}", id)
     }
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+/// Author of a [`Comment`], see [`PartialUser`].
+pub type CommentAuthor = PartialUser;
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
 struct CommentParent {
     id: String,
 
@@ -0,0 +1,84 @@
+//! ISO 3166-1 country codes
+#![warn(missing_docs)]
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+/// An ISO 3166-1 alpha-2 country code, like `DE` or `FR`.
+///
+/// Codes are normalized to upper case.
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::models::CountryCode;
+/// let country: CountryCode = "fr".parse().unwrap();
+///
+/// assert_eq!(country.as_str(), "FR");
+/// assert!("FRA".parse::<CountryCode>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CountryCode([u8; 2]);
+
+/// The error returned when parsing an invalid [`CountryCode`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid country code `{0}`")]
+pub struct ParseCountryCodeError(String);
+
+impl CountryCode {
+    /// Returns the code, e.g. `DE`
+    pub fn as_str(&self) -> &str {
+        // only ascii letters are accepted when parsing
+        std::str::from_utf8(&self.0).unwrap_or_default()
+    }
+}
+
+impl FromStr for CountryCode {
+    type Err = ParseCountryCodeError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        match code.as_bytes() {
+            [first, second] if first.is_ascii_alphabetic() && second.is_ascii_alphabetic() => {
+                Ok(CountryCode([first.to_ascii_uppercase(), second.to_ascii_uppercase()]))
+            }
+            _ => Err(ParseCountryCodeError(code.to_string())),
+        }
+    }
+}
+
+impl TryFrom<&str> for CountryCode {
+    type Error = ParseCountryCodeError;
+
+    fn try_from(code: &str) -> Result<Self, Self::Error> {
+        code.parse()
+    }
+}
+
+impl AsRef<str> for CountryCode {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for CountryCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for CountryCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for CountryCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+
+        code.parse().map_err(serde::de::Error::custom)
+    }
+}
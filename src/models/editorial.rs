@@ -1,6 +1,6 @@
 //! [Editorial API](https://developers.deezer.com/api/editorial)
 #![warn(missing_docs)]
-use crate::models::{DeezerEnumerable, DeezerObject};
+use crate::models::{DeezerEnumerable, DeezerObject, GenreId, PartialAlbum, RecordType};
 use serde::{Deserialize, Serialize};
 
 /// Contains all the information provided for an Editorial.
@@ -61,10 +61,11 @@ use serde::{Deserialize, Serialize};
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct Editorial {
     /// The editorial's Deezer id
-    pub id: u64,
+    pub id: GenreId,
 
     /// The editorial's name
     pub name: String,
@@ -85,8 +86,92 @@ pub struct Editorial {
     pub picture_xl: String,
 }
 
+impl Editorial {
+    /// Creates an editorial with the given id and name, all other fields are left empty.
+    pub fn new(id: impl Into<GenreId>, name: impl Into<String>) -> Self {
+        Editorial {
+            id: id.into(),
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Selects the new releases of an editorial, see [`DeezerClient::editorial_releases()`](crate::DeezerClient::editorial_releases).
+///
+/// Dates are given as `YYYY-MM-DD`, like the api formats them.
+/// Releases without a release date only match filters without a date range.
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::models::{PartialAlbum, RecordType, ReleaseFilter};
+/// let filter = ReleaseFilter::new()
+///     .released_from("2024-03-04")
+///     .released_until("2024-03-10")
+///     .record_type(RecordType::Album);
+///
+/// let mut album = PartialAlbum::new(302127, "Discovery");
+/// album.release_date = Some("2024-03-08".to_string());
+/// album.record_type = Some(RecordType::Album);
+/// assert!(filter.matches(&album));
+///
+/// album.record_type = Some(RecordType::Single);
+/// assert!(!filter.matches(&album));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ReleaseFilter {
+    released_from: Option<String>,
+    released_until: Option<String>,
+    record_types: Vec<RecordType>,
+}
+
+impl ReleaseFilter {
+    /// Creates a filter matching all releases.
+    pub fn new() -> Self {
+        ReleaseFilter::default()
+    }
+
+    /// Only matches releases on or after the given date.
+    pub fn released_from(mut self, date: impl Into<String>) -> Self {
+        self.released_from = Some(date.into());
+        self
+    }
+
+    /// Only matches releases on or before the given date.
+    pub fn released_until(mut self, date: impl Into<String>) -> Self {
+        self.released_until = Some(date.into());
+        self
+    }
+
+    /// Only matches releases of the given record type, can be called multiple times to match several types.
+    pub fn record_type(mut self, record_type: RecordType) -> Self {
+        self.record_types.push(record_type);
+        self
+    }
+
+    /// Returns whether the album matches the filter.
+    pub fn matches(&self, album: &PartialAlbum) -> bool {
+        let has_range = self.released_from.is_some() || self.released_until.is_some();
+        // dates in the `YYYY-MM-DD` format sort chronologically as strings
+        let in_range = match album.release_date.as_deref() {
+            Some(date) => {
+                self.released_from.as_deref().is_none_or(|from| date >= from)
+                    && self.released_until.as_deref().is_none_or(|until| date <= until)
+            }
+            None => !has_range,
+        };
+        let has_record_type = self.record_types.is_empty()
+            || album.record_type.as_ref().is_some_and(|record_type| self.record_types.contains(record_type));
+
+        in_range && has_record_type
+    }
+}
+
 impl DeezerObject for Editorial {
-    fn get_api_url(id: u64) -> String {
+    type Id = GenreId;
+
+    fn get_api_url(id: GenreId) -> String {
         format!("editorial/{}", id)
     }
 }
@@ -2,7 +2,8 @@
 #![warn(missing_docs)]
 use serde::{Deserialize, Serialize};
 
-use crate::models::{DeezerEnumerable, DeezerObject};
+use crate::models::{Chart, DeezerEnumerable, DeezerObject, GenreId};
+use crate::{DeezerClient, Result};
 
 /// Contains all the information provided for a Genre.
 ///
@@ -62,10 +63,11 @@ use crate::models::{DeezerEnumerable, DeezerObject};
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct Genre {
     /// The editorial's Deezer id
-    pub id: u64,
+    pub id: GenreId,
 
     /// The editorial's name
     pub name: String,
@@ -86,8 +88,39 @@ pub struct Genre {
     pub picture_xl: String,
 }
 
+impl Genre {
+    /// Creates a genre with the given id and name, all other fields are left empty.
+    pub fn new(id: impl Into<GenreId>, name: impl Into<String>) -> Self {
+        Genre {
+            id: id.into(),
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Fetches the charts of the genre, see [`DeezerClient::chart_for_genre()`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let genre = deezer.genre(132).await?.unwrap();
+    /// let chart = genre.fetch_charts(&deezer).await?;
+    /// # assert!(!chart.tracks.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_charts(&self, client: &DeezerClient) -> Result<Chart> {
+        client.chart_for_genre(self.id).await
+    }
+}
+
 impl DeezerObject for Genre {
-    fn get_api_url(id: u64) -> String {
+    type Id = GenreId;
+
+    fn get_api_url(id: GenreId) -> String {
         format!("genre/{}", id)
     }
 }
@@ -0,0 +1,306 @@
+//! Strongly typed ids of the api objects
+#![warn(missing_docs)]
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+macro_rules! deezer_id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+        #[serde(transparent)]
+        pub struct $name(u64);
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                crate::serde_helpers::id(deserializer).map($name)
+            }
+        }
+
+        impl $name {
+            /// Wraps the given raw id
+            pub const fn new(id: u64) -> Self {
+                $name(id)
+            }
+
+            /// Returns the raw id
+            pub const fn get(self) -> u64 {
+                self.0
+            }
+        }
+
+        impl From<u64> for $name {
+            fn from(id: u64) -> Self {
+                $name(id)
+            }
+        }
+
+        impl From<$name> for u64 {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl PartialEq<u64> for $name {
+            fn eq(&self, other: &u64) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.parse().map($name)
+            }
+        }
+    };
+}
+
+deezer_id! {
+    /// The id of an [`Album`](crate::models::Album)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use deezer::models::AlbumId;
+    /// let id: AlbumId = "302127".parse().unwrap();
+    ///
+    /// assert_eq!(id, AlbumId::new(302127));
+    /// assert_eq!(id.to_string(), "302127");
+    /// assert_eq!(serde_json::to_string(&id).unwrap(), "302127");
+    /// ```
+    ///
+    /// Ids are parsed from numbers as well as from strings:
+    ///
+    /// ```rust
+    /// # use deezer::models::AlbumId;
+    /// let ids: Vec<AlbumId> = serde_json::from_str(r#"[302127, "302127"]"#).unwrap();
+    ///
+    /// assert_eq!(ids[0], ids[1]);
+    /// ```
+    AlbumId
+}
+
+deezer_id! {
+    /// The id of an [`Artist`](crate::models::Artist)
+    ArtistId
+}
+
+deezer_id! {
+    /// The id of a [`Track`](crate::models::Track)
+    TrackId
+}
+
+deezer_id! {
+    /// The id of a [`Genre`](crate::models::Genre)
+    ///
+    /// Charts and editorials are identified by the id of their genre.
+    GenreId
+}
+
+impl GenreId {
+    /// The pseudo genre containing all genres, e.g. for the overall charts
+    pub const ALL: GenreId = GenreId(0);
+}
+
+deezer_id! {
+    /// The id of a [`Playlist`](crate::models::Playlist)
+    PlaylistId
+}
+
+deezer_id! {
+    /// The id of a [`User`](crate::models::User)
+    UserId
+}
+
+/// The id of any api object with a typed id
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::models::{AnyId, TrackId};
+/// let id = AnyId::from(TrackId::new(3135556));
+///
+/// assert_eq!(id.to_string(), "track/3135556");
+/// assert_eq!(id.get(), 3135556);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum AnyId {
+    /// The id of an [`Album`](crate::models::Album)
+    Album(AlbumId),
+
+    /// The id of an [`Artist`](crate::models::Artist)
+    Artist(ArtistId),
+
+    /// The id of a [`Playlist`](crate::models::Playlist)
+    Playlist(PlaylistId),
+
+    /// The id of a [`Track`](crate::models::Track)
+    Track(TrackId),
+
+    /// The id of a [`User`](crate::models::User)
+    User(UserId),
+}
+
+impl AnyId {
+    /// Returns the raw id
+    pub fn get(self) -> u64 {
+        match self {
+            AnyId::Album(id) => id.get(),
+            AnyId::Artist(id) => id.get(),
+            AnyId::Playlist(id) => id.get(),
+            AnyId::Track(id) => id.get(),
+            AnyId::User(id) => id.get(),
+        }
+    }
+
+    /// Returns the type of the object as used in api paths, e.g. `album`
+    pub fn kind(self) -> &'static str {
+        match self {
+            AnyId::Album(_) => "album",
+            AnyId::Artist(_) => "artist",
+            AnyId::Playlist(_) => "playlist",
+            AnyId::Track(_) => "track",
+            AnyId::User(_) => "user",
+        }
+    }
+
+    /// Creates the id for the given type as used in api paths
+    pub(crate) fn from_kind(kind: &str, id: u64) -> Option<AnyId> {
+        match kind {
+            "album" => Some(AnyId::Album(id.into())),
+            "artist" => Some(AnyId::Artist(id.into())),
+            "playlist" => Some(AnyId::Playlist(id.into())),
+            "track" => Some(AnyId::Track(id.into())),
+            "user" | "profile" => Some(AnyId::User(id.into())),
+            _ => None,
+        }
+    }
+}
+
+/// Formats the id as its api path, e.g. `album/302127`
+impl fmt::Display for AnyId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.kind(), self.get())
+    }
+}
+
+impl From<AlbumId> for AnyId {
+    fn from(id: AlbumId) -> Self {
+        AnyId::Album(id)
+    }
+}
+
+impl From<ArtistId> for AnyId {
+    fn from(id: ArtistId) -> Self {
+        AnyId::Artist(id)
+    }
+}
+
+impl From<PlaylistId> for AnyId {
+    fn from(id: PlaylistId) -> Self {
+        AnyId::Playlist(id)
+    }
+}
+
+impl From<TrackId> for AnyId {
+    fn from(id: TrackId) -> Self {
+        AnyId::Track(id)
+    }
+}
+
+impl From<UserId> for AnyId {
+    fn from(id: UserId) -> Self {
+        AnyId::User(id)
+    }
+}
+
+/// Conversion of ids, numbers and links into the id of an api object.
+///
+/// Allows passing links pasted by users directly to the lookups of the [`DeezerClient`](crate::DeezerClient).
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::models::{AlbumId, IntoDeezerId};
+/// let from_link: AlbumId = "https://www.deezer.com/en/album/302127".into_deezer_id().unwrap();
+/// let from_number: AlbumId = 302127.into_deezer_id().unwrap();
+///
+/// assert_eq!(from_link, from_number);
+/// assert!(IntoDeezerId::<AlbumId>::into_deezer_id("https://www.deezer.com/en/track/3135556").is_err());
+/// ```
+pub trait IntoDeezerId<T> {
+    /// Returns the id, fails for links to other objects and malformed input.
+    fn into_deezer_id(self) -> crate::Result<T>;
+}
+
+impl<T: From<u64>> IntoDeezerId<T> for u64 {
+    fn into_deezer_id(self) -> crate::Result<T> {
+        Ok(T::from(self))
+    }
+}
+
+impl<T> IntoDeezerId<T> for String
+where
+    for<'a> &'a str: IntoDeezerId<T>,
+{
+    fn into_deezer_id(self) -> crate::Result<T> {
+        self.as_str().into_deezer_id()
+    }
+}
+
+impl<T> IntoDeezerId<T> for &String
+where
+    for<'a> &'a str: IntoDeezerId<T>,
+{
+    fn into_deezer_id(self) -> crate::Result<T> {
+        self.as_str().into_deezer_id()
+    }
+}
+
+macro_rules! into_deezer_id {
+    ($name:ident, $variant:ident) => {
+        impl IntoDeezerId<$name> for $name {
+            fn into_deezer_id(self) -> crate::Result<$name> {
+                Ok(self)
+            }
+        }
+
+        impl IntoDeezerId<$name> for AnyId {
+            fn into_deezer_id(self) -> crate::Result<$name> {
+                match self {
+                    AnyId::$variant(id) => Ok(id),
+                    id => Err(crate::DeezerError::InvalidId(id.to_string())),
+                }
+            }
+        }
+
+        /// Accepts a numeric id or a link to a deezer.com page
+        impl IntoDeezerId<$name> for &str {
+            fn into_deezer_id(self) -> crate::Result<$name> {
+                if let Ok(id) = self.trim().parse() {
+                    return Ok(id);
+                }
+                crate::links::parse_url(self)
+                    .ok_or_else(|| crate::DeezerError::InvalidId(self.to_string()))?
+                    .into_deezer_id()
+            }
+        }
+    };
+}
+
+into_deezer_id!(AlbumId, Album);
+into_deezer_id!(ArtistId, Artist);
+into_deezer_id!(PlaylistId, Playlist);
+into_deezer_id!(TrackId, Track);
+into_deezer_id!(UserId, User);
@@ -0,0 +1,77 @@
+//! Sizes of the pictures and covers provided by the api
+#![warn(missing_docs)]
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+const IMAGE_CDN_URL: &str = "https://e-cdns-images.dzcdn.net/images";
+
+/// Builds the url of an image on deezer's image cdn.
+///
+/// The cdn renders the image identified by `md5` in any requested size.
+/// `kind` is the image type, e.g. `cover`, `artist` or `playlist`.
+pub(crate) fn cdn_image_url(kind: &str, md5: &str, pixels: u32) -> String {
+    format!("{}/{}/{}/{}x{}-000000-80-0-0.jpg", IMAGE_CDN_URL, kind, md5, pixels, pixels)
+}
+
+/// Size of a picture or cover.
+///
+/// Each api object with an image provides one url per size,
+/// use the `image_url()` methods to select one of them.
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::models::*;
+/// let mut album = Album::new(302127, "Discovery");
+/// album.cover_xl = "https://e-cdns-images.dzcdn.net/images/cover/2e018122cb56986277102d2041a592c8/1000x1000-000000-80-0-0.jpg".to_string();
+///
+/// assert_eq!(album.image_url(ImageSize::Xl), album.cover_xl);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageSize {
+    /// 56x56 pixels
+    Small,
+
+    /// 250x250 pixels
+    #[default]
+    Medium,
+
+    /// 500x500 pixels
+    Big,
+
+    /// 1000x1000 pixels
+    Xl,
+}
+
+impl ImageSize {
+    /// All sizes, from smallest to largest
+    pub const ALL: [ImageSize; 4] = [ImageSize::Small, ImageSize::Medium, ImageSize::Big, ImageSize::Xl];
+
+    /// Returns the name used by the api for this size
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImageSize::Small => "small",
+            ImageSize::Medium => "medium",
+            ImageSize::Big => "big",
+            ImageSize::Xl => "xl",
+        }
+    }
+
+    /// Returns the width and height of this size in pixels
+    pub fn pixels(&self) -> u32 {
+        match self {
+            ImageSize::Small => 56,
+            ImageSize::Medium => 250,
+            ImageSize::Big => 500,
+            ImageSize::Xl => 1000,
+        }
+    }
+}
+
+impl fmt::Display for ImageSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -1,7 +1,12 @@
 //! [Infos API](https://developers.deezer.com/api/infos)
 #![warn(missing_docs)]
+use std::fmt;
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
+use crate::models::CountryCode;
+
 /// Contains all the information about the API in the current country.
 ///
 /// # Examples
@@ -18,7 +23,8 @@ use serde::{Deserialize, Serialize};
 /// # }
 ///
 /// ```
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct Infos {
     /// The current country ISO code
     pub country_iso: String,
@@ -31,18 +37,164 @@ pub struct Infos {
 
     /// An array of available offers in the current country
     pub offers: Vec<Offer>,
+
+    /// The token authorizing uploads like playlist pictures, only issued to authenticated requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload_token: Option<String>,
+
+    /// The number of seconds the [`upload_token`](Infos::upload_token) is valid
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload_token_lifetime: Option<u64>,
+}
+
+impl Infos {
+    /// Returns the parsed [`country_iso`](Infos::country_iso).
+    ///
+    /// Returns `None` when the code isn't a valid ISO 3166-1 alpha-2 code.
+    pub fn country_code(&self) -> Option<CountryCode> {
+        self.country_iso.parse().ok()
+    }
 }
 
 /// Contains all the information provided for an Offer.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct Offer {
+    /// The offer's id
+    #[serde(deserialize_with = "crate::serde_helpers::id")]
     pub id: u64,
+
+    /// The offer's name
     pub name: String,
+
+    /// The price of the offer
     pub amount: String,
+
+    /// The currency of the price
     pub currency: String,
+
+    /// The price formatted for display
     pub displayed_amount: String,
+
+    /// The terms and conditions of the offer
     pub tc: String,
+
+    /// The terms and conditions of the offer as html
     pub tc_html: String,
+
+    /// The terms and conditions of the offer as plain text
     pub tc_txt: String,
+
+    /// The try and buy period of the offer
     pub try_and_buy: u64,
 }
+
+impl Offer {
+    /// Returns the parsed price of the offer.
+    ///
+    /// Returns `None` if the [`amount`](Offer::amount) is not a valid decimal.
+    pub fn price(&self) -> Option<Money> {
+        Money::parse(&self.amount, &self.currency)
+    }
+
+    /// Returns whether the offer can be tried before buying.
+    pub fn has_trial(&self) -> bool {
+        self.try_and_buy > 0
+    }
+
+    /// Returns the length of the try and buy period, which deezer specifies in days.
+    pub fn trial_period(&self) -> Option<Duration> {
+        if self.has_trial() {
+            Some(Duration::from_secs(self.try_and_buy * 24 * 60 * 60))
+        } else {
+            None
+        }
+    }
+}
+
+/// A price in a specific currency.
+///
+/// The amount is kept in the minor unit of the currency, whose number of decimals is taken from ISO 4217,
+/// e.g. cents for `EUR`, yen for `JPY` and fils for `KWD`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::models::Money;
+/// let price = Money::parse("9.99", "EUR").unwrap();
+///
+/// assert_eq!(price.minor_units, 999);
+/// assert_eq!(price.to_string(), "9.99 EUR");
+/// assert_eq!(Money::parse("10", "EUR").unwrap().minor_units, 1000);
+/// assert_eq!(Money::parse("1200", "JPY").unwrap().to_string(), "1200 JPY");
+/// assert_eq!(Money::parse("2.5", "KWD").unwrap().to_string(), "2.500 KWD");
+/// assert_eq!(Money::parse("9.99", "JPY"), None);
+/// assert_eq!(Money::parse("free", "EUR"), None);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Money {
+    /// The amount in the minor unit of the currency, see [`exponent()`](Money::exponent)
+    pub minor_units: u64,
+
+    /// The ISO 4217 code of the currency
+    pub currency: String,
+}
+
+impl Money {
+    /// Parses a decimal amount like `9.99`.
+    ///
+    /// Returns `None` when the amount has more significant fractional digits than the currency has decimals.
+    pub fn parse(amount: &str, currency: &str) -> Option<Money> {
+        let exponent = minor_unit_exponent(currency) as usize;
+        let mut parts = amount.trim().splitn(2, '.');
+        let units: u64 = parts.next()?.parse().ok()?;
+        let fraction = match parts.next() {
+            None => "",
+            Some(fraction) if fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) => return None,
+            Some(fraction) if fraction.len() > exponent => {
+                let (significant, rest) = fraction.split_at(exponent);
+                if rest.bytes().any(|b| b != b'0') {
+                    return None;
+                }
+                significant
+            }
+            Some(fraction) => fraction,
+        };
+        let minor = format!("{:0<width$}", fraction, width = exponent);
+        let minor: u64 = if minor.is_empty() { 0 } else { minor.parse().ok()? };
+
+        Some(Money {
+            minor_units: units.checked_mul(10u64.pow(exponent as u32))?.checked_add(minor)?,
+            currency: currency.to_string(),
+        })
+    }
+
+    /// Returns the number of decimals of the currency, e.g. `2` for `EUR` or `0` for `JPY`.
+    pub fn exponent(&self) -> u32 {
+        minor_unit_exponent(&self.currency)
+    }
+}
+
+/// Returns the ISO 4217 minor unit of the currency, defaulting to two decimals.
+fn minor_unit_exponent(currency: &str) -> u32 {
+    match currency.to_ascii_uppercase().as_str() {
+        "BIF" | "CLP" | "DJF" | "GNF" | "ISK" | "JPY" | "KMF" | "KRW" | "PYG" | "RWF" | "UGX" | "UYI" | "VND"
+        | "VUV" | "XAF" | "XOF" | "XPF" => 0,
+        "BHD" | "IQD" | "JOD" | "KWD" | "LYD" | "OMR" | "TND" => 3,
+        "CLF" | "UYW" => 4,
+        _ => 2,
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let exponent = self.exponent();
+        if exponent == 0 {
+            return write!(f, "{} {}", self.minor_units, self.currency);
+        }
+        let scale = 10u64.pow(exponent);
+
+        write!(f, "{}.{:0width$} {}", self.minor_units / scale, self.minor_units % scale, self.currency,
+               width = exponent as usize)
+    }
+}
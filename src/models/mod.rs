@@ -1,11 +1,31 @@
 //! Contains all api objects
 #![warn(missing_docs)]
+use std::fmt;
+use std::marker::PhantomData;
+
 use async_trait::async_trait;
+use serde::de::{self, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Serialize};
 
+use crate::fetch::BatchResult;
 use crate::DeezerClient;
 use crate::Result;
 
+/// Implements [`Hash`](std::hash::Hash) by the id of an api object.
+///
+/// This is consistent with the derived `Eq` implementations, as equal objects share their id.
+macro_rules! hash_by_id {
+    ($($model:ty),* $(,)?) => {
+        $(
+            impl std::hash::Hash for $model {
+                fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                    self.id.hash(state);
+                }
+            }
+        )*
+    };
+}
+
 #[doc(inline)]
 pub use self::album::*;
 #[doc(inline)]
@@ -15,10 +35,16 @@ pub use self::chart::*;
 #[doc(inline)]
 pub use self::comment::*;
 #[doc(inline)]
+pub use self::country::*;
+#[doc(inline)]
 pub use self::editorial::*;
 #[doc(inline)]
 pub use self::genre::*;
 #[doc(inline)]
+pub use self::ids::*;
+#[doc(inline)]
+pub use self::image::*;
+#[doc(inline)]
 pub use self::infos::*;
 #[doc(inline)]
 pub use self::options::*;
@@ -29,36 +55,203 @@ pub use self::radio::*;
 #[doc(inline)]
 pub use self::track::*;
 #[doc(inline)]
+#[cfg(feature = "url")]
+pub use self::urls::DeezerUrls;
+#[doc(inline)]
 pub use self::user::*;
+use std::iter::FromIterator;
 use std::ops::Deref;
 
+#[doc(inline)]
+pub use deezer_derive::DeezerPartial;
+
 pub mod album;
 pub mod artist;
 pub mod chart;
 pub mod comment;
+pub mod country;
 pub mod editorial;
 pub mod genre;
+pub mod ids;
+pub mod image;
 pub mod infos;
 pub mod options;
 pub mod playlist;
 pub mod radio;
 pub mod track;
+#[cfg(feature = "url")]
+pub mod urls;
 pub mod user;
 
+hash_by_id!(
+    Album,
+    AlbumGenre,
+    Artist,
+    ChartPlaylist,
+    Comment,
+    ContributorArtist,
+    Editorial,
+    Genre,
+    Offer,
+    PartialAlbum,
+    PartialArtist,
+    PartialPlaylist,
+    PartialTrack,
+    PartialUser,
+    Playlist,
+    Radio,
+    TrackArtist,
+    User,
+);
+
 /// Wrapper around deezer array types
 ///
 /// Some deezer models return an object with a `data` property containing the actual array.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A few endpoints return a bare array instead, both are accepted when deserializing.
+///
+/// ```rust
+/// # use deezer::models::DeezerArray;
+/// let wrapped: DeezerArray<u64> = serde_json::from_str(r#"{"data": [1, 2, 3]}"#).unwrap();
+/// let bare: DeezerArray<u64> = serde_json::from_str("[1, 2, 3]").unwrap();
+/// assert_eq!(wrapped, bare);
+///
+/// // errors of the items aren't hidden behind the shape detection
+/// let err = serde_json::from_str::<DeezerArray<u64>>(r#"{"data": [1, "two"]}"#).unwrap_err();
+/// assert!(err.to_string().starts_with("invalid type: string \"two\", expected u64"));
+///
+/// let mut collected: DeezerArray<u64> = (1..=2).collect();
+/// collected.extend(Some(3));
+/// assert_eq!(collected, bare);
+/// ```
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct DeezerArray<T> {
+    /// The wrapped items
     pub data: Vec<T>,
+
+    /// The total number of items, when the array is paginated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+
+    /// The url of the next page, when there are more items
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
 }
 
 impl<T> DeezerArray<T> {
+    /// Returns an iterator over the wrapped items
     pub fn iter(&self) -> std::slice::Iter<'_, T> {
         self.data.iter()
     }
 }
 
+impl<T> DeezerArray<T>
+where
+    T: DeezerPartial,
+{
+    /// Resolves all items to their full objects.
+    ///
+    /// At most `concurrency` requests are in flight at the same time. The requests are sent with `client`,
+    /// so they also wait for its [rate limiter](DeezerClient::with_rate_limiter) when one is configured.
+    /// Items failing to resolve, e.g. because their full object can no longer be found,
+    /// are reported in [`BatchResult::failed`] instead of failing the whole array.
+    pub async fn get_all_full(&self, client: &DeezerClient, concurrency: usize) -> BatchResult<T::Full, <T::Full as DeezerObject>::Id> {
+        client
+            .get_entities(self.iter().map(DeezerPartial::id), concurrency)
+            .await
+    }
+}
+
+impl<T> Default for DeezerArray<T> {
+    fn default() -> Self {
+        DeezerArray::from(Vec::new())
+    }
+}
+
+impl<T> From<Vec<T>> for DeezerArray<T> {
+    fn from(data: Vec<T>) -> Self {
+        DeezerArray {
+            data,
+            total: None,
+            next: None,
+        }
+    }
+}
+
+impl<T> FromIterator<T> for DeezerArray<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        DeezerArray::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T> Extend<T> for DeezerArray<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.data.extend(iter)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for DeezerArray<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(DeezerArrayVisitor(PhantomData))
+    }
+}
+
+/// Accepts both shapes a [`DeezerArray`] is returned in by the api.
+///
+/// Errors of the items are passed on unchanged, unlike with an untagged enum.
+struct DeezerArrayVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for DeezerArrayVisitor<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = DeezerArray<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an array or an object with a `data` array")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut data = Vec::with_capacity(seq.size_hint().unwrap_or_default().min(1024));
+        while let Some(item) = seq.next_element()? {
+            data.push(item);
+        }
+
+        Ok(DeezerArray::from(data))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut data = None;
+        let mut total = None;
+        let mut next = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "data" => data = Some(map.next_value()?),
+                "total" => total = map.next_value()?,
+                "next" => next = map.next_value()?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        let data = data.ok_or_else(|| de::Error::missing_field("data"))?;
+
+        Ok(DeezerArray { data, total, next })
+    }
+}
+
 impl<T> Deref for DeezerArray<T> {
     type Target = [T];
 
@@ -85,15 +278,42 @@ impl<T> IntoIterator for DeezerArray<T> {
 /// A by id queryable api object of the deezer api
 #[async_trait]
 pub trait DeezerObject: serde::de::DeserializeOwned {
+    /// The type of the id this object is queryable by
+    type Id: From<u64> + Copy + Send + Sync + std::fmt::Display;
+
     /// Get a relative api url for the given `id`
-    fn get_api_url(id: u64) -> String;
+    fn get_api_url(id: Self::Id) -> String;
 
     /// Fetch an api object with the given `id`
     async fn get(id: u64) -> Result<Option<Self>> {
         let client = DeezerClient::new();
 
+        client.get_entity(id.into()).await
+    }
+
+    /// Fetch an api object with the given `id` using the given client.
+    ///
+    /// Unlike [`get()`](DeezerObject::get) the request honors the configuration of the client,
+    /// e.g. its access token, rate limiter and cache.
+    async fn get_with(id: Self::Id, client: &DeezerClient) -> Result<Option<Self>> {
         client.get_entity(id).await
     }
+
+    /// Checks whether an api object with the given `id` exists, without parsing it.
+    ///
+    /// ```rust
+    /// # use deezer::models::*;
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// assert!(Album::exists(AlbumId::new(302127), &deezer).await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn exists(id: Self::Id, client: &DeezerClient) -> Result<bool> {
+        client.entity_exists::<Self>(id).await
+    }
 }
 
 /// A by upc queryable api object of the deezer api
@@ -110,11 +330,36 @@ pub trait DeezerUpcObject: serde::de::DeserializeOwned {
     }
 }
 
-// Represents an api object which has a list method
+/// A by isrc queryable api object of the deezer api
+#[async_trait]
+pub trait DeezerIsrcObject: serde::de::DeserializeOwned {
+    /// Get a relative api url for the given `isrc`
+    fn get_api_url(isrc: Isrc) -> String;
+
+    /// Fetch an api object with the given `isrc`
+    async fn get_by_isrc(isrc: Isrc) -> Result<Option<Self>> {
+        let client = DeezerClient::new();
+
+        client.get_entity_by_isrc(isrc).await
+    }
+}
+
+/// A subset of an api object which references the full [`DeezerObject`]
+pub trait DeezerPartial {
+    /// The full api object
+    type Full: DeezerObject;
+
+    /// The id of the full api object
+    fn id(&self) -> <Self::Full as DeezerObject>::Id;
+}
+
+/// Represents an api object which has a list method
 #[async_trait]
 pub trait DeezerEnumerable: DeezerObject {
+    /// Get a relative api url listing all objects
     fn get_all_api_url() -> String;
 
+    /// Fetch all api objects
     async fn get_all() -> Result<Vec<Self>> {
         let client = DeezerClient::new();
 
@@ -122,4 +367,38 @@ pub trait DeezerEnumerable: DeezerObject {
     }
 }
 
+/// Universal Product Code of an album
 pub type Upc = String;
+
+/// International Standard Recording Code of a track
+pub type Isrc = String;
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::{json, Value};
+
+    /// Values of unexpected types and ranges the api might send for a numeric code.
+    pub(crate) fn odd_codes() -> Vec<Value> {
+        let mut codes = vec![
+            json!(null),
+            json!(true),
+            json!(false),
+            json!(""),
+            json!(" "),
+            json!("none"),
+            json!("1.5"),
+            json!("0x1"),
+            json!(1.5),
+            json!(-0.5),
+            json!(u64::MAX),
+            json!(i64::MIN),
+            json!([1]),
+            json!({ "code": 1 }),
+        ];
+        codes.extend((-64i64..64).map(|code| json!(code)));
+        codes.extend((-64i64..64).map(|code| json!(code.to_string())));
+        codes.extend((-64i64..64).map(|code| json!(format!(" {} ", code))));
+
+        codes
+    }
+}
@@ -1,5 +1,7 @@
 //! [Options API](https://developers.deezer.com/api/options)
 #![warn(missing_docs)]
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
 /// Contains all the information provided for a user's Options.
@@ -18,7 +20,8 @@ use serde::{Deserialize, Serialize};
 /// # }
 ///
 /// ```
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct Options {
     /// If the user can stream on the platform
     pub streaming: bool,
@@ -57,3 +60,10 @@ pub struct Options {
     /// Allows to stream the radio
     pub radio: bool,
 }
+
+impl Options {
+    /// Returns the streaming duration of the user.
+    pub fn streaming_time(&self) -> Duration {
+        Duration::from_secs(self.streaming_duration)
+    }
+}
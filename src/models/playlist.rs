@@ -1,7 +1,15 @@
 //! [Playlists API](https://developers.deezer.com/api/playlists)
 #![warn(missing_docs)]
-use crate::models::{Album, Artist, DeezerArray, DeezerObject, Track, User};
-use crate::Result;
+use std::fmt;
+use std::time::Duration;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+
+use crate::export::{self, ExportTrack};
+use crate::models::image::cdn_image_url;
+use crate::models::{DeezerArray, DeezerObject, DeezerPartial, ImageSize, PartialAlbum, PartialArtist, PartialTrack, PartialUser, PlaylistId, UserId};
+use crate::{DeezerClient, Result};
 use serde::{Deserialize, Serialize};
 
 /// Contains all the information provided for a Playlist.
@@ -35,10 +43,11 @@ use serde::{Deserialize, Serialize};
 /// # }
 ///
 /// ```
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct Playlist {
     /// The playlist's Deezer id
-    pub id: u64,
+    pub id: PlaylistId,
 
     /// The playlist's title
     pub title: String,
@@ -62,14 +71,14 @@ pub struct Playlist {
     pub is_collaborative: bool,
 
     /// The playlist's rate
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rating: Option<u64>,
 
     /// Number of tracks in the playlist
     pub nb_tracks: u64,
 
     /// Number of tracks not seen
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub unseen_track_count: Option<u64>,
 
     /// The number of playlist's fans
@@ -79,7 +88,9 @@ pub struct Playlist {
     pub link: String,
 
     /// The share link of the playlist on Deezer
-    #[serde(rename = "share")]
+    ///
+    /// Empty when not returned by the api, use [`share_url()`](Playlist::share_url) to fall back to the link.
+    #[serde(rename = "share", default)]
     pub share_link: String,
 
     /// The url of the playlist's cover
@@ -97,6 +108,18 @@ pub struct Playlist {
     /// The url of the playlist's cover in size xl
     pub picture_xl: String,
 
+    /// The md5 hash of the playlist's cover image
+    #[serde(default, deserialize_with = "crate::serde_helpers::empty_string_as_none", skip_serializing_if = "Option::is_none")]
+    pub md5_image: Option<String>,
+
+    /// The type of the playlist's cover image (e.g. playlist or cover)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub picture_type: Option<String>,
+
+    /// The date the playlist was created at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creation_date: Option<String>,
+
     /// The checksum for the track list
     pub checksum: String,
 
@@ -108,158 +131,267 @@ pub struct Playlist {
 }
 
 impl DeezerObject for Playlist {
-    fn get_api_url(id: u64) -> String {
+    type Id = PlaylistId;
+
+    fn get_api_url(id: PlaylistId) -> String {
         format!("playlist/{}", id)
     }
 }
 
-/// Subset of [`User`].
-///
-/// Use [`get_full()`] for the full [`User`].
-///
-/// [`get_full()`]: PlaylistUser::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct PlaylistUser {
-    /// The user's Deezer ID
-    pub id: u64,
-
-    /// The user's Deezer nickname
-    pub name: String,
-}
-
-impl PlaylistUser {
-    /// Returns the corresponding [`User`].
-    pub async fn get_full(&self) -> Result<User> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let user = User::get(self.id).await?.unwrap();
-        Ok(user)
+/// Formats the playlist as `Title by Creator (12 tracks)`.
+impl fmt::Display for Playlist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} by {} ({} tracks)", self.title, self.creator.name, self.nb_tracks)
     }
 }
 
-/// Subset of [`Track`].
-///
-/// Use [`get_full()`] for the full [`Track`].
-///
-/// [`get_full()`]: PlaylistTrack::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct PlaylistTrack {
-    /// `The track's Deezer id`
-    pub id: u64,
+impl Playlist {
+    /// Creates a playlist with the given id and title, all other fields are left empty.
+    pub fn new(id: impl Into<PlaylistId>, title: impl Into<String>) -> Self {
+        Playlist {
+            id: id.into(),
+            title: title.into(),
+            ..Default::default()
+        }
+    }
 
-    /// `True if the track is readable in the player for the current user`
-    pub readable: bool,
+    /// Returns the url of the cover in the given size.
+    pub fn image_url(&self, size: ImageSize) -> &str {
+        match size {
+            ImageSize::Small => &self.picture_small,
+            ImageSize::Medium => &self.picture_medium,
+            ImageSize::Big => &self.picture_big,
+            ImageSize::Xl => &self.picture_xl,
+        }
+    }
 
-    /// `The track's fulltitle`
-    pub title: String,
+    /// Returns the url of the playlist's cover in a custom size of `pixels`x`pixels`, see [`Album::cover_url()`](crate::models::Album::cover_url).
+    ///
+    /// Returns `None` without an [`md5_image`](Playlist::md5_image).
+    pub fn cover_url(&self, pixels: u32) -> Option<String> {
+        let kind = self.picture_type.as_deref().unwrap_or("playlist");
+        self.md5_image
+            .as_deref()
+            .map(|md5| cdn_image_url(kind, md5, pixels))
+    }
 
-    /// `The track's short title`
-    pub title_short: String,
+    /// Returns the share link of the playlist, falling back to its [`link`](Playlist::link).
+    pub fn share_url(&self) -> &str {
+        if self.share_link.is_empty() {
+            &self.link
+        } else {
+            &self.share_link
+        }
+    }
 
-    /// `The track's version`
-    pub title_version: Option<String>,
+    /// Returns the playlist's duration.
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(self.duration_in_seconds)
+    }
 
-    /// `The track's unseen status`
-    #[serde(default)]
-    pub unseen: bool,
+    /// Renders the playlist's tracks as an [XSPF](https://xspf.org/spec) document.
+    ///
+    /// Uses the embedded [`tracks`](Playlist::tracks), to include the fields only full tracks provide
+    /// fetch them and use [`export::to_xspf()`](crate::export::to_xspf) instead.
+    pub fn to_xspf(&self) -> String {
+        export::to_xspf(Some(&self.title), self.tracks.iter().map(ExportTrack::from))
+    }
 
-    /// `The url of the track on Deezer`
-    pub link: String,
+    /// Renders the playlist's tracks as an extended M3U playlist, see [`export::to_m3u()`].
+    pub fn to_m3u(&self) -> String {
+        export::to_m3u(self.tracks.iter().map(ExportTrack::from))
+    }
 
-    /// `The track's duration in seconds`
-    #[serde(rename = "duration")]
-    pub duration_in_seconds: u64,
+    /// Renders the playlist's tracks as CSV, see [`export::to_csv()`].
+    pub fn to_csv(&self) -> String {
+        export::to_csv(self.tracks.iter().map(ExportTrack::from))
+    }
 
-    /// `The track's Deezer rank`
-    pub rank: u64,
+    /// Fetches the users which can edit the playlist, separating the creator from the contributors.
+    ///
+    /// The api doesn't list the collaborators of a playlist. On collaborative playlists every user
+    /// which added the playlist to their favorites may add tracks, so its fans are reported as
+    /// contributors. Playlists which aren't collaborative have no contributors and aren't requested.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let playlist = deezer.playlist(908622995).await?.unwrap();
+    /// let members = playlist.fetch_members(&deezer).await?;
+    /// # assert!(members.is_member(playlist.creator.id));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_members(&self, client: &DeezerClient) -> Result<PlaylistMembers> {
+        let mut contributors = if self.is_collaborative {
+            client.playlist_fans(self.id).await?
+        } else {
+            Vec::new()
+        };
+        contributors.retain(|user| user.id != self.creator.id);
+
+        Ok(PlaylistMembers {
+            creator: self.creator.clone(),
+            contributors,
+        })
+    }
+}
 
-    /// `Whether the track contains explicit lyrics`
-    #[serde(rename = "explicit_lyrics")]
-    pub has_explicit_lyrics: bool,
+/// The users of a playlist, see [`Playlist::fetch_members()`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct PlaylistMembers {
+    /// The user which created the playlist
+    pub creator: PlaylistUser,
 
-    /// `The url of track's preview file. This file contains the first 30 seconds of the track`
-    #[serde(default)]
-    pub preview_url: String,
+    /// The users other than the creator which can add tracks to the collaborative playlist
+    pub contributors: Vec<PartialUser>,
+}
 
-    /// `The time when the track has been added to the playlist`
-    #[serde(rename = "time_add")]
-    pub added_on: u64,
+impl PlaylistMembers {
+    /// Returns whether the user is the creator or one of the contributors.
+    pub fn is_member(&self, user: impl Into<UserId>) -> bool {
+        let user = user.into();
 
-    /// `Artist Object`
-    pub artist: PlaylistTrackArtist,
+        self.creator.id == user || self.is_contributor(user)
+    }
 
-    /// `Album Object`
-    pub album: PlaylistTrackAlbum,
-}
+    /// Returns whether the user is one of the contributors, which excludes the creator.
+    pub fn is_contributor(&self, user: impl Into<UserId>) -> bool {
+        let user = user.into();
 
-impl PlaylistTrack {
-    /// Returns the full [`Track`].
-    pub async fn get_full(&self) -> Result<Track> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let track = Track::get(self.id).await?.unwrap();
-        Ok(track)
+        self.contributors.iter().any(|contributor| contributor.id == user)
     }
 }
 
-/// Subset of [`Artist`].
+/// Subset of [`Playlist`].
+///
+/// Embedded in other api objects, which fields are available depends on the embedding api.
 ///
-/// Use [`get_full()`] for the full [`Artist`].
+/// Use [`get_full()`] for the full [`Playlist`].
 ///
-/// [`get_full()`]: PlaylistTrackArtist::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct PlaylistTrackArtist {
-    /// `The artist's Deezer id`
-    pub id: u64,
+/// [`get_full()`]: PartialPlaylist::get_full
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default, DeezerPartial)]
+#[deezer(full = Playlist)]
+#[non_exhaustive]
+pub struct PartialPlaylist {
+    /// The playlist's Deezer id
+    pub id: PlaylistId,
+
+    /// The playlist's title
+    pub title: String,
 
-    /// `The artist's name`
-    pub name: String,
+    /// The playlist's duration in seconds
+    #[serde(rename = "duration", default, skip_serializing_if = "Option::is_none")]
+    pub duration_in_seconds: Option<u64>,
 
-    /// `The url of the artist on Deezer`
-    pub link: String,
-}
+    /// If the playlist is public or not
+    #[serde(rename = "public", default, skip_serializing_if = "Option::is_none")]
+    pub is_public: Option<bool>,
 
-impl PlaylistTrackArtist {
-    /// Returns the full [`Artist`].
-    pub async fn get_full(&self) -> Result<Artist> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let artist = Artist::get(self.id).await?.unwrap();
-        Ok(artist)
-    }
-}
+    /// If the playlist is the love tracks playlist
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_loved_track: Option<bool>,
 
-/// Subset of [`Album`].
-///
-/// Use [`get_full()`] for the full [`Album`].
-///
-/// [`get_full()`]: PlaylistTrackAlbum::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct PlaylistTrackAlbum {
-    /// `The Deezer album id`
-    pub id: u64,
+    /// If the playlist is collaborative or not
+    #[serde(rename = "collaborative", default, skip_serializing_if = "Option::is_none")]
+    pub is_collaborative: Option<bool>,
 
-    /// `The album title`
-    pub title: String,
+    /// Nb tracks in the playlist
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nb_tracks: Option<u64>,
 
-    /// `The url of the album's cover.`
-    pub cover: String,
+    /// The url of the playlist on Deezer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
 
-    /// `The url of the album's cover in size small.`
-    pub cover_small: String,
+    /// The url of the playlist's cover
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub picture: Option<String>,
 
-    /// `The url of the album's cover in size medium.`
-    pub cover_medium: String,
+    /// The url of the playlist's cover in size small
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub picture_small: Option<String>,
 
-    /// `The url of the album's cover in size big.`
-    pub cover_big: String,
+    /// The url of the playlist's cover in size medium
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub picture_medium: Option<String>,
 
-    /// `The url of the album's cover in size xl.`
-    pub cover_xl: String,
+    /// The url of the playlist's cover in size big
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub picture_big: Option<String>,
+
+    /// The url of the playlist's cover in size xl
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub picture_xl: Option<String>,
+
+    /// The checksum for the track list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+
+    /// The creator of the playlist
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creator: Option<PlaylistUser>,
+
+    /// The time when the playlist has been added to the user's favorites
+    #[serde(rename = "time_add", default, skip_serializing_if = "Option::is_none")]
+    pub added_on: Option<u64>,
 }
 
-impl PlaylistTrackAlbum {
-    /// Returns the full [`Album`].
-    pub async fn get_full(&self) -> Result<Album> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let album = Album::get(self.id).await?.unwrap();
-        Ok(album)
+impl PartialPlaylist {
+    /// Creates a playlist with the given id and title, all other fields are left empty.
+    pub fn new(id: impl Into<PlaylistId>, title: impl Into<String>) -> Self {
+        PartialPlaylist {
+            id: id.into(),
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the url of the cover in the given size.
+    ///
+    /// Returns `None` when the embedding api doesn't provide this size.
+    pub fn image_url(&self, size: ImageSize) -> Option<&str> {
+        match size {
+            ImageSize::Small => self.picture_small.as_deref(),
+            ImageSize::Medium => self.picture_medium.as_deref(),
+            ImageSize::Big => self.picture_big.as_deref(),
+            ImageSize::Xl => self.picture_xl.as_deref(),
+        }
+    }
+
+    /// Returns the time when the playlist has been added to the user's favorites.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use deezer::models::PartialPlaylist;
+    /// let playlist: PartialPlaylist = serde_json::from_str(r#"{
+    ///     "id": 908622995,
+    ///     "title": "En mode 60",
+    ///     "time_add": 1600000000
+    /// }"#).unwrap();
+    ///
+    /// assert_eq!(playlist.added_at().map(|added| added.timestamp()), Some(1600000000));
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn added_at(&self) -> Option<DateTime<Utc>> {
+        self.added_on.and_then(crate::dates::from_timestamp)
     }
 }
+
+/// Creator of a [`Playlist`], see [`PartialUser`].
+pub type PlaylistUser = PartialUser;
+
+/// Track of a [`Playlist`], see [`PartialTrack`].
+pub type PlaylistTrack = PartialTrack;
+
+/// Artist of a [`PlaylistTrack`], see [`PartialArtist`].
+pub type PlaylistTrackArtist = PartialArtist;
+
+/// Album of a [`PlaylistTrack`], see [`PartialAlbum`].
+pub type PlaylistTrackAlbum = PartialAlbum;
+
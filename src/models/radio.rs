@@ -1,6 +1,7 @@
 //! [Radio API](https://developers.deezer.com/api/radio)
 #![warn(missing_docs)]
-use crate::models::{DeezerEnumerable, DeezerObject};
+use crate::models::{DeezerEnumerable, DeezerObject, PartialTrack};
+use crate::{DeezerClient, Result};
 use serde::{Deserialize, Serialize};
 
 /// Contains all the information provided for a Radio.
@@ -61,9 +62,11 @@ use serde::{Deserialize, Serialize};
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct Radio {
     /// The radio deezer ID
+    #[serde(deserialize_with = "crate::serde_helpers::id")]
     pub id: u64,
 
     /// The radio title
@@ -100,7 +103,40 @@ pub struct Radio {
     pub track_list: String,
 }
 
+impl Radio {
+    /// Creates a radio with the given id and title, all other fields are left empty.
+    pub fn new(id: u64, title: impl Into<String>) -> Self {
+        Radio {
+            id,
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Fetches the next tracks of the radio from its [`track_list`](Radio::track_list).
+    ///
+    /// Each call returns a new selection of tracks, so a station can be played by fetching repeatedly.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let radio = deezer.radio(6).await?.unwrap();
+    /// let tracks = radio.fetch_tracks(&deezer).await?;
+    /// # assert!(!tracks.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_tracks(&self, client: &DeezerClient) -> Result<Vec<PartialTrack>> {
+        client.get_list_from_url(&self.track_list, None, None).await
+    }
+}
+
 impl DeezerObject for Radio {
+    type Id = u64;
+
     fn get_api_url(id: u64) -> String {
         format!("radio/{}", id)
     }
@@ -1,9 +1,17 @@
 //! [Track API](https://developers.deezer.com/api/track)
 #![warn(missing_docs)]
-use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
 
-use crate::models::{Album, Artist, ContributorArtist, DeezerObject};
-use crate::Result;
+use bytes::Bytes;
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::Stream;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::models::image::cdn_image_url;
+use crate::models::{Artist, ArtistId, ContributorArtist, DeezerIsrcObject, DeezerObject, DeezerPartial, Isrc, PartialAlbum, PartialArtist, TrackId};
+use crate::{DeezerClient, Result};
 
 /// Contains all the information provided for a Track.
 ///
@@ -35,10 +43,11 @@ use crate::Result;
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+#[non_exhaustive]
 pub struct Track {
     /// `The track's Deezer id`
-    pub id: u64,
+    pub id: TrackId,
 
     /// `True if the track is readable in the player for the current user`
     pub readable: bool,
@@ -53,7 +62,7 @@ pub struct Track {
     pub title_version: String,
 
     /// `The track's unseen status`
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub unseen: Option<bool>,
 
     /// `The track's isrc`
@@ -88,10 +97,22 @@ pub struct Track {
     #[serde(rename = "explicit_lyrics")]
     pub has_explicit_lyrics: bool,
 
+    /// `The explicit content level of the track's lyrics`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explicit_content_lyrics: Option<ExplicitContentLevel>,
+
+    /// `The explicit content level of the track's cover`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explicit_content_cover: Option<ExplicitContentLevel>,
+
     /// `The url of track's preview file. This file contains the first 30 seconds of the track`
-    #[serde(default)]
+    #[serde(rename = "preview", alias = "preview_url", default, deserialize_with = "crate::serde_helpers::empty_string_as_none", skip_serializing_if = "Option::is_none")]
     pub preview_url: Option<String>,
 
+    /// `The md5 hash of the track's cover image`
+    #[serde(default, deserialize_with = "crate::serde_helpers::empty_string_as_none", skip_serializing_if = "Option::is_none")]
+    pub md5_image: Option<String>,
+
     /// `Beats per minute`
     pub bpm: f32,
 
@@ -102,9 +123,8 @@ pub struct Track {
     pub available_countries: Vec<String>,
 
     /// `Return an alternative readable track if the current track is not readable`
-    #[serde(rename = "alternative")]
-    #[serde(default)]
-    pub alternative_track_id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alternative: Option<Box<PartialTrack>>,
 
     /// `Return a list of contributors on the track`
     pub contributors: Vec<ContributorArtist>,
@@ -117,20 +137,416 @@ pub struct Track {
 }
 
 impl DeezerObject for Track {
-    fn get_api_url(id: u64) -> String {
+    type Id = TrackId;
+
+    fn get_api_url(id: TrackId) -> String {
         format!("track/{}", id)
     }
 }
 
+impl DeezerIsrcObject for Track {
+    fn get_api_url(isrc: Isrc) -> String {
+        format!("track/isrc:{}", isrc)
+    }
+}
+
+/// Formats the track as `Artist – Title (3:45)`.
+///
+/// ```rust
+/// # use deezer::models::*;
+/// let mut track = Track::new(3135556, "Harder, Better, Faster, Stronger");
+/// track.artist = TrackArtist::new(27, "Daft Punk");
+/// track.duration_in_seconds = 224;
+///
+/// assert_eq!(track.to_string(), "Daft Punk – Harder, Better, Faster, Stronger (3:44)");
+/// ```
+impl fmt::Display for Track {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let minutes = self.duration_in_seconds / 60;
+        let seconds = self.duration_in_seconds % 60;
+
+        write!(f, "{} – {} ({}:{:02})", self.artist.name, self.title, minutes, seconds)
+    }
+}
+
+impl Track {
+    /// Creates a track with the given id and title, all other fields are left empty.
+    pub fn new(id: impl Into<TrackId>, title: impl Into<String>) -> Self {
+        Track {
+            id: id.into(),
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the url of the album cover in a custom size of `pixels`x`pixels`, see [`Album::cover_url()`](crate::models::Album::cover_url).
+    ///
+    /// Returns `None` without an [`md5_image`](Track::md5_image).
+    pub fn cover_url(&self, pixels: u32) -> Option<String> {
+        self.md5_image
+            .as_deref()
+            .map(|md5| cdn_image_url("cover", md5, pixels))
+    }
+
+    /// Returns the track's album's disk number.
+    ///
+    /// Alias of [`album_disk_number`](Track::album_disk_number).
+    pub fn disk_number(&self) -> u64 {
+        self.album_disk_number
+    }
+
+    /// Returns the position of the track on its album's disk.
+    ///
+    /// Alias of [`track_position_in_album`](Track::track_position_in_album).
+    pub fn track_position(&self) -> u64 {
+        self.track_position_in_album
+    }
+
+    /// Returns the number of countries the track is available in.
+    pub fn available_countries_count(&self) -> usize {
+        self.available_countries.len()
+    }
+
+    /// Returns `true` when the track is available in the country with the given ISO 3166 code.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::models::Track;
+    /// let mut track = Track::new(3135556, "Harder, Better, Faster, Stronger");
+    /// track.available_countries = vec!["DE".to_string(), "FR".to_string()];
+    ///
+    /// assert!(track.is_available_in("fr"));
+    /// assert!(!track.is_available_in("US"));
+    /// ```
+    pub fn is_available_in(&self, country: impl AsRef<str>) -> bool {
+        let country = country.as_ref();
+        self.available_countries
+            .iter()
+            .any(|available| available.eq_ignore_ascii_case(country))
+    }
+
+    /// Returns a track which is readable for the current user.
+    ///
+    /// This is the track itself when it's [`readable`](Track::readable),
+    /// otherwise its [`alternative`](Track::alternative) is fetched.
+    /// Returns `None` when neither is readable.
+    pub async fn resolve_readable(&self, client: &DeezerClient) -> Result<Option<Track>> {
+        if self.readable {
+            return Ok(Some(self.clone()));
+        }
+        let alternative = match &self.alternative {
+            Some(alternative) => alternative.get_full(client).await?,
+            None => None,
+        };
+
+        Ok(alternative.filter(|track| track.readable))
+    }
+
+    /// Returns the track's duration.
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(self.duration_in_seconds)
+    }
+
+    /// Returns the parsed [`release_date`](Track::release_date).
+    ///
+    /// Returns `None` when the release date is unknown.
+    #[cfg(feature = "chrono")]
+    pub fn released_on(&self) -> Option<NaiveDate> {
+        crate::dates::parse_date(&self.release_date)
+    }
+
+    /// Streams the track's 30 second preview, without buffering the whole file.
+    ///
+    /// Returns `None` when the track has no [`preview_url`](Track::preview_url).
+    /// The download is subject to the client's rate limiter, the access token isn't sent to the cdn.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use futures::TryStreamExt;
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let track = deezer.track(3135556).await?.unwrap();
+    /// if let Some(preview) = track.preview_stream(&deezer).await? {
+    ///     let chunks: Vec<_> = preview.try_collect().await?;
+    ///     assert!(!chunks.is_empty());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn preview_stream(&self, client: &DeezerClient) -> Result<Option<impl Stream<Item = Result<Bytes>>>> {
+        match &self.preview_url {
+            Some(url) => client.get_bytes_stream(url).await.map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// The explicit content level of the lyrics or the cover of a [`Track`] or an [`Album`](crate::models::Album).
+///
+/// Levels unknown to this crate are kept as [`ExplicitContentLevel::Unknown`] with the value deezer sent,
+/// which is serialized unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// # use serde_json::json;
+/// # use deezer::models::ExplicitContentLevel;
+/// let levels: Vec<ExplicitContentLevel> = serde_json::from_str(r#"[0, "1", 3, 42, -1, "none"]"#).unwrap();
+///
+/// assert_eq!(levels, vec![
+///     ExplicitContentLevel::NotExplicit,
+///     ExplicitContentLevel::Explicit,
+///     ExplicitContentLevel::Edited,
+///     ExplicitContentLevel::Unknown(json!(42)),
+///     ExplicitContentLevel::Unknown(json!(-1)),
+///     ExplicitContentLevel::Unknown(json!("none")),
+/// ]);
+/// assert_eq!(serde_json::to_string(&levels).unwrap(), r#"[0,1,3,42,-1,"none"]"#);
+/// assert!(ExplicitContentLevel::PartiallyExplicit.is_explicit());
+/// ```
+///
+/// Unexpected levels don't fail the whole [`Track`], `null` is read as no level:
+///
+/// ```rust
+/// # use deezer::models::{ExplicitContentLevel, Track};
+/// let mut json = serde_json::to_value(Track::new(3135556, "Harder, Better, Faster, Stronger")).unwrap();
+/// json["explicit_content_lyrics"] = serde_json::json!(-1);
+/// json["explicit_content_cover"] = serde_json::Value::Null;
+///
+/// let track: Track = serde_json::from_value(json.clone()).unwrap();
+/// assert_eq!(track.explicit_content_lyrics, Some(ExplicitContentLevel::Unknown((-1).into())));
+/// assert_eq!(track.explicit_content_cover, None);
+///
+/// json["explicit_content_lyrics"] = serde_json::json!("unknown");
+/// let track: Track = serde_json::from_value(json).unwrap();
+/// assert_eq!(track.explicit_content_lyrics, Some(ExplicitContentLevel::Unknown("unknown".into())));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExplicitContentLevel {
+    /// `0`, the content is not explicit
+    NotExplicit,
+
+    /// `1`, the content is explicit
+    Explicit,
+
+    /// `2`, deezer doesn't know whether the content is explicit
+    Undetermined,
+
+    /// `3`, an edited version of explicit content
+    Edited,
+
+    /// `4`, parts of the content are explicit
+    PartiallyExplicit,
+
+    /// `5`, deezer doesn't know whether parts of the content are explicit
+    PartiallyUndetermined,
+
+    /// `6`, no advice is available
+    NoAdviceAvailable,
+
+    /// `7`, no advice is available for parts of the content
+    PartiallyNoAdviceAvailable,
+
+    /// A level not known to this crate, holding the value sent by deezer
+    Unknown(serde_json::Value),
+}
+
+impl ExplicitContentLevel {
+    /// Returns whether the content is at least partially explicit.
+    pub fn is_explicit(&self) -> bool {
+        matches!(self, ExplicitContentLevel::Explicit | ExplicitContentLevel::PartiallyExplicit)
+    }
+
+    /// Returns the numeric level as used by the api, `None` for unknown levels which aren't a number.
+    pub fn code(&self) -> Option<u64> {
+        match self {
+            ExplicitContentLevel::NotExplicit => Some(0),
+            ExplicitContentLevel::Explicit => Some(1),
+            ExplicitContentLevel::Undetermined => Some(2),
+            ExplicitContentLevel::Edited => Some(3),
+            ExplicitContentLevel::PartiallyExplicit => Some(4),
+            ExplicitContentLevel::PartiallyUndetermined => Some(5),
+            ExplicitContentLevel::NoAdviceAvailable => Some(6),
+            ExplicitContentLevel::PartiallyNoAdviceAvailable => Some(7),
+            ExplicitContentLevel::Unknown(level) => level.as_u64(),
+        }
+    }
+}
+
+impl From<u64> for ExplicitContentLevel {
+    fn from(level: u64) -> Self {
+        match level {
+            0 => ExplicitContentLevel::NotExplicit,
+            1 => ExplicitContentLevel::Explicit,
+            2 => ExplicitContentLevel::Undetermined,
+            3 => ExplicitContentLevel::Edited,
+            4 => ExplicitContentLevel::PartiallyExplicit,
+            5 => ExplicitContentLevel::PartiallyUndetermined,
+            6 => ExplicitContentLevel::NoAdviceAvailable,
+            7 => ExplicitContentLevel::PartiallyNoAdviceAvailable,
+            level => ExplicitContentLevel::Unknown(level.into()),
+        }
+    }
+}
+
+/// Levels are also accepted as numeric strings, any other value is kept unchanged.
+impl<'de> Deserialize<'de> for ExplicitContentLevel {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let level = serde_json::Value::deserialize(deserializer)?;
+
+        Ok(match crate::serde_helpers::lenient_code(&level) {
+            Ok(Some(code @ 0..=7)) => ExplicitContentLevel::from(code),
+            _ => ExplicitContentLevel::Unknown(level),
+        })
+    }
+}
+
+impl Serialize for ExplicitContentLevel {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            ExplicitContentLevel::Unknown(level) => level.serialize(serializer),
+            level => serializer.serialize_u64(level.code().expect("known levels have a code")),
+        }
+    }
+}
+
+/// Subset of [`Track`].
+///
+/// Embedded in other api objects, which fields are available depends on the embedding api.
+///
+/// Use [`get_full()`] for the full [`Track`].
+///
+/// Serializing produces the same field names the api uses, fields which were absent stay absent:
+///
+/// ```rust
+/// # use deezer::models::PartialTrack;
+/// let json = r#"{"id":3135556,"title":"Harder, Better, Faster, Stronger","duration":224,"preview":"https://cdns-preview-d.dzcdn.net/stream/c-deda7fa9316d9e9e880d2c6207e92260-8.mp3","artist":{"id":27,"name":"Daft Punk"}}"#;
+///
+/// let track: PartialTrack = serde_json::from_str(json).unwrap();
+/// assert_eq!(track.duration_in_seconds, 224);
+/// assert!(track.preview_url.is_some());
+///
+/// assert_eq!(serde_json::to_string(&track).unwrap(), json);
+/// ```
+///
+/// [`get_full()`]: PartialTrack::get_full
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default, DeezerPartial)]
+#[deezer(full = Track)]
+#[non_exhaustive]
+pub struct PartialTrack {
+    /// `The track's Deezer id`
+    pub id: TrackId,
+
+    /// `True if the track is readable in the player for the current user`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readable: Option<bool>,
+
+    /// `The track's full title`
+    pub title: String,
+
+    /// `The track's short title`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_short: Option<String>,
+
+    /// `The track's version`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_version: Option<String>,
+
+    /// `The track's unseen status`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unseen: Option<bool>,
+
+    /// `The url of the track on Deezer`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+
+    /// `The track's duration in seconds`
+    #[serde(rename = "duration")]
+    pub duration_in_seconds: u64,
+
+    /// `The track's Deezer rank`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rank: Option<u64>,
+
+    /// `Whether the track contains explicit lyrics`
+    #[serde(rename = "explicit_lyrics", default, skip_serializing_if = "Option::is_none")]
+    pub has_explicit_lyrics: Option<bool>,
+
+    /// `The url of track's preview file. This file contains the first 30 seconds of the track`
+    #[serde(rename = "preview", default, deserialize_with = "crate::serde_helpers::empty_string_as_none", skip_serializing_if = "Option::is_none")]
+    pub preview_url: Option<String>,
+
+    /// `The md5 hash of the track's cover image`
+    #[serde(default, deserialize_with = "crate::serde_helpers::empty_string_as_none", skip_serializing_if = "Option::is_none")]
+    pub md5_image: Option<String>,
+
+    /// `The position of the track in the charts`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<u64>,
+
+    /// `The time when the track has been added to the playlist or the user's favorites`
+    #[serde(rename = "time_add", default, skip_serializing_if = "Option::is_none")]
+    pub added_on: Option<u64>,
+
+    /// `Artist Object`
+    pub artist: PartialArtist,
+
+    /// `Album Object`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub album: Option<PartialAlbum>,
+}
+
+impl PartialTrack {
+    /// Creates a track with the given id and title, all other fields are left empty.
+    pub fn new(id: impl Into<TrackId>, title: impl Into<String>) -> Self {
+        PartialTrack {
+            id: id.into(),
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the url of the album cover in a custom size of `pixels`x`pixels`, see [`Album::cover_url()`](crate::models::Album::cover_url).
+    ///
+    /// Returns `None` without an [`md5_image`](PartialTrack::md5_image).
+    pub fn cover_url(&self, pixels: u32) -> Option<String> {
+        self.md5_image
+            .as_deref()
+            .map(|md5| cdn_image_url("cover", md5, pixels))
+    }
+
+    /// Returns the track's duration.
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(self.duration_in_seconds)
+    }
+
+    /// Returns the time when the track has been added to the playlist or the user's favorites.
+    #[cfg(feature = "chrono")]
+    pub fn added_at(&self) -> Option<DateTime<Utc>> {
+        self.added_on.and_then(crate::dates::from_timestamp)
+    }
+
+    /// Fetches only the contributors of the track, see [`DeezerClient::track_contributors()`].
+    ///
+    /// Returns an empty list when the track doesn't exist anymore.
+    pub async fn fetch_contributors(&self, client: &DeezerClient) -> Result<Vec<ContributorArtist>> {
+        Ok(client.track_contributors(self.id).await?.unwrap_or_default())
+    }
+}
+
 /// Subset of [`Artist`].
 ///
 /// Use [`get_full()`] for the full [`Artist`].
 ///
 /// [`get_full()`]: TrackArtist::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default, DeezerPartial)]
+#[deezer(full = Artist)]
+#[non_exhaustive]
 pub struct TrackArtist {
     /// `The artist's Deezer id`
-    pub id: u64,
+    pub id: ArtistId,
 
     /// `The artist's name`
     pub name: String,
@@ -158,11 +574,11 @@ pub struct TrackArtist {
     pub picture_xl: String,
 
     /// `The number of artist's albums`
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub nb_album: Option<u64>,
 
     /// `The number of artist's fans`
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub nb_fan: Option<u64>,
 
     /// `True if the artist has a smartradio`
@@ -174,54 +590,91 @@ pub struct TrackArtist {
 }
 
 impl TrackArtist {
-    /// Returns the corresponding [`Artist`].
-    pub async fn get_full(&self) -> Result<Artist> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let artist = Artist::get(self.id).await?.unwrap();
-        Ok(artist)
+    /// Creates an artist with the given id and name, all other fields are left empty.
+    pub fn new(id: impl Into<ArtistId>, name: impl Into<String>) -> Self {
+        TrackArtist {
+            id: id.into(),
+            name: name.into(),
+            ..Default::default()
+        }
     }
 }
 
-/// Subset of [`Album`].
-///
-/// Use [`get_full()`] for the full [`Album`].
-///
-/// [`get_full()`]: TrackAlbum::get_full
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct TrackAlbum {
-    /// `The Deezer album id`
-    pub id: u64,
-
-    /// `The album title`
-    pub title: String,
-
-    /// `The url of the album on Deezer`
-    pub link: String,
-
-    /// `The url of the album's cover.`
-    pub cover: String,
-
-    /// `The url of the album's cover in size small.`
-    pub cover_small: String,
-
-    /// `The url of the album's cover in size medium.`
-    pub cover_medium: String,
-
-    /// `The url of the album's cover in size big.`
-    pub cover_big: String,
+/// Album of a [`Track`], see [`PartialAlbum`].
+pub type TrackAlbum = PartialAlbum;
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::models::tests::odd_codes;
+
+    #[test]
+    fn levels_keep_unknown_values() {
+        for code in odd_codes() {
+            let level: ExplicitContentLevel = serde_json::from_value(code.clone()).unwrap();
+            let serialized = serde_json::to_value(&level).unwrap();
+
+            match &level {
+                ExplicitContentLevel::Unknown(unknown) => {
+                    assert_eq!(unknown, &code);
+                    assert_eq!(serialized, code);
+                }
+                known => assert_eq!(serialized, json!(known.code().unwrap())),
+            }
+            assert_eq!(serde_json::from_value::<ExplicitContentLevel>(serialized).unwrap(), level);
+        }
+    }
 
-    /// `The url of the album's cover in size xl.`
-    pub cover_xl: String,
+    #[test]
+    fn alternatives_are_embedded_tracks() {
+        let mut track = serde_json::to_value(Track::new(3135556, "Harder, Better, Faster, Stronger")).unwrap();
+        track["readable"] = json!(false);
+        track["alternative"] = json!({
+            "id": 3135557,
+            "readable": true,
+            "title": "Harder, Better, Faster, Stronger",
+            "duration": 224,
+            "artist": { "id": 27, "name": "Daft Punk" },
+            "type": "track",
+        });
+
+        let track: Track = serde_json::from_value(track).unwrap();
+        let alternative = track.alternative.unwrap();
+        assert_eq!(alternative.id, TrackId::new(3135557));
+        assert_eq!(alternative.readable, Some(true));
+    }
 
-    /// `The album's release date`
-    pub release_date: String,
-}
+    #[test]
+    fn empty_previews_are_missing() {
+        let mut track = serde_json::to_value(Track::new(3135556, "Harder, Better, Faster, Stronger")).unwrap();
+        track["preview"] = json!("");
+        track["md5_image"] = json!("");
+        let partial = json!({ "id": 3135556, "title": "Harder, Better, Faster, Stronger", "duration": 224, "preview": "", "md5_image": "", "artist": { "id": 27, "name": "Daft Punk" } });
+
+        let track: Track = serde_json::from_value(track).unwrap();
+        let partial: PartialTrack = serde_json::from_value(partial).unwrap();
+        assert_eq!(track.preview_url, None);
+        assert_eq!(track.cover_url(500), None);
+        assert_eq!(partial.preview_url, None);
+        assert_eq!(partial.cover_url(500), None);
+    }
 
-impl TrackAlbum {
-    /// Returns the full [`Album`].
-    pub async fn get_full(&self) -> Result<Album> {
-        // Safety: unwrap should be okay here, as the artist is referenced by the deezer api
-        let album = Album::get(self.id).await?.unwrap();
-        Ok(album)
+    #[test]
+    fn tracks_with_odd_levels_deserialize() {
+        let mut track = serde_json::to_value(Track::new(3135556, "Harder, Better, Faster, Stronger")).unwrap();
+        for code in odd_codes() {
+            track["explicit_content_lyrics"] = code.clone();
+            track["explicit_content_cover"] = code.clone();
+
+            let parsed: Track = serde_json::from_value(track.clone()).unwrap();
+            let expected = match code {
+                Value::Null => None,
+                code => Some(ExplicitContentLevel::deserialize(&code).unwrap()),
+            };
+            assert_eq!(parsed.explicit_content_lyrics, expected);
+            assert_eq!(parsed.explicit_content_cover, expected);
+        }
     }
 }
@@ -0,0 +1,185 @@
+//! The links of the api objects as parsed [`Url`]s
+#![warn(missing_docs)]
+pub use url::Url;
+
+use crate::models::*;
+
+/// Typed access to the links of an api object.
+///
+/// The links are parsed leniently: empty or malformed links are returned as `None`
+/// instead of failing the deserialization of the whole object.
+///
+/// # Examples
+/// ```rust
+/// # use deezer::models::{Album, DeezerUrls};
+/// let mut album = Album::new(302127, "Discovery");
+/// album.link = "https://www.deezer.com/album/302127".to_string();
+///
+/// assert_eq!(album.link_url().unwrap().path(), "/album/302127");
+/// assert_eq!(album.share_link_url(), None);
+/// ```
+pub trait DeezerUrls {
+    /// The page of the object on deezer.com
+    fn link_url(&self) -> Option<Url>;
+
+    /// The link to share the object with
+    fn share_link_url(&self) -> Option<Url> {
+        None
+    }
+
+    /// The api url listing the tracks of the object
+    fn tracklist_url(&self) -> Option<Url> {
+        None
+    }
+
+    /// The picture or cover of the object in the given size
+    fn picture_url(&self, _size: ImageSize) -> Option<Url> {
+        None
+    }
+}
+
+fn parse<'a>(url: impl Into<Option<&'a str>>) -> Option<Url> {
+    url.into().and_then(|url| Url::parse(url).ok())
+}
+
+impl DeezerUrls for Album {
+    fn link_url(&self) -> Option<Url> {
+        parse(self.link.as_str())
+    }
+
+    fn share_link_url(&self) -> Option<Url> {
+        parse(self.share_link.as_str())
+    }
+
+    fn tracklist_url(&self) -> Option<Url> {
+        parse(self.tracklist_api_url.as_str())
+    }
+
+    fn picture_url(&self, size: ImageSize) -> Option<Url> {
+        parse(self.image_url(size))
+    }
+}
+
+impl DeezerUrls for PartialAlbum {
+    fn link_url(&self) -> Option<Url> {
+        parse(self.link.as_deref())
+    }
+
+    fn picture_url(&self, size: ImageSize) -> Option<Url> {
+        parse(self.image_url(size))
+    }
+}
+
+impl DeezerUrls for Artist {
+    fn link_url(&self) -> Option<Url> {
+        parse(self.link.as_str())
+    }
+
+    fn share_link_url(&self) -> Option<Url> {
+        parse(self.share_link.as_str())
+    }
+
+    fn tracklist_url(&self) -> Option<Url> {
+        parse(self.tracklist.as_str())
+    }
+
+    fn picture_url(&self, size: ImageSize) -> Option<Url> {
+        parse(self.image_url(size))
+    }
+}
+
+impl DeezerUrls for PartialArtist {
+    fn link_url(&self) -> Option<Url> {
+        parse(self.link.as_deref())
+    }
+
+    fn tracklist_url(&self) -> Option<Url> {
+        parse(self.tracklist.as_deref())
+    }
+
+    fn picture_url(&self, size: ImageSize) -> Option<Url> {
+        parse(self.image_url(size))
+    }
+}
+
+impl DeezerUrls for Playlist {
+    fn link_url(&self) -> Option<Url> {
+        parse(self.link.as_str())
+    }
+
+    fn share_link_url(&self) -> Option<Url> {
+        parse(self.share_link.as_str())
+    }
+
+    fn picture_url(&self, size: ImageSize) -> Option<Url> {
+        parse(self.image_url(size))
+    }
+}
+
+impl DeezerUrls for PartialPlaylist {
+    fn link_url(&self) -> Option<Url> {
+        parse(self.link.as_deref())
+    }
+
+    fn picture_url(&self, size: ImageSize) -> Option<Url> {
+        parse(self.image_url(size))
+    }
+}
+
+impl DeezerUrls for Radio {
+    fn link_url(&self) -> Option<Url> {
+        None
+    }
+
+    fn share_link_url(&self) -> Option<Url> {
+        parse(self.share_link.as_deref())
+    }
+
+    fn tracklist_url(&self) -> Option<Url> {
+        parse(self.track_list.as_str())
+    }
+}
+
+impl DeezerUrls for Track {
+    fn link_url(&self) -> Option<Url> {
+        parse(self.link.as_str())
+    }
+
+    fn share_link_url(&self) -> Option<Url> {
+        parse(self.share_link.as_str())
+    }
+}
+
+impl DeezerUrls for PartialTrack {
+    fn link_url(&self) -> Option<Url> {
+        parse(self.link.as_deref())
+    }
+}
+
+impl DeezerUrls for User {
+    fn link_url(&self) -> Option<Url> {
+        parse(self.link.as_str())
+    }
+
+    fn tracklist_url(&self) -> Option<Url> {
+        parse(self.track_list.as_str())
+    }
+
+    fn picture_url(&self, size: ImageSize) -> Option<Url> {
+        parse(self.image_url(size))
+    }
+}
+
+impl DeezerUrls for PartialUser {
+    fn link_url(&self) -> Option<Url> {
+        parse(self.link.as_deref())
+    }
+
+    fn tracklist_url(&self) -> Option<Url> {
+        parse(self.track_list.as_deref())
+    }
+
+    fn picture_url(&self, size: ImageSize) -> Option<Url> {
+        parse(self.image_url(size))
+    }
+}
@@ -1,7 +1,10 @@
 //! [User API](https://developers.deezer.com/api/user)
 #![warn(missing_docs)]
-use crate::models::DeezerObject;
-use serde::{Deserialize, Serialize};
+use crate::models::{DeezerObject, DeezerPartial, ImageSize, PartialTrack, UserId};
+use crate::{DeezerClient, Result};
+#[cfg(feature = "chrono")]
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Contains all the information provided for a User.
 ///
@@ -34,10 +37,11 @@ use serde::{Deserialize, Serialize};
 /// # }
 ///
 /// ```
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct User {
     /// The user's Deezer ID
-    pub id: u64,
+    pub id: UserId,
 
     /// The user's Deezer nickname
     pub name: String,
@@ -58,7 +62,7 @@ pub struct User {
 
     /// The user's status
     #[serde(default)]
-    pub status: u64,
+    pub status: UserStatus,
 
     /// The user's birthday
     #[serde(default)]
@@ -68,9 +72,9 @@ pub struct User {
     #[serde(default)]
     pub inscription_date: String,
 
-    /// The user's gender : F or M
+    /// The user's gender
     #[serde(default)]
-    pub gender: String,
+    pub gender: Gender,
 
     /// The url of the profil for the user on Deezer
     pub link: String,
@@ -106,8 +110,361 @@ pub struct User {
     pub track_list: String,
 }
 
+/// The subscription status of a [`User`].
+///
+/// Statuses unknown to this crate are kept as [`UserStatus::Unknown`] with the value deezer sent,
+/// which is serialized unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// # use serde_json::json;
+/// # use deezer::models::UserStatus;
+/// assert_eq!(UserStatus::from(1), UserStatus::Premium);
+/// assert_eq!(UserStatus::from(42), UserStatus::Unknown(json!(42)));
+/// assert_eq!(UserStatus::PremiumPlus.code(), Some(2));
+///
+/// let statuses: Vec<UserStatus> = serde_json::from_str(r#"[1, "2", 7, null, -1, "premium"]"#).unwrap();
+/// assert_eq!(statuses, vec![
+///     UserStatus::Premium,
+///     UserStatus::PremiumPlus,
+///     UserStatus::Unknown(json!(7)),
+///     UserStatus::Unknown(json!(null)),
+///     UserStatus::Unknown(json!(-1)),
+///     UserStatus::Unknown(json!("premium")),
+/// ]);
+/// assert_eq!(serde_json::to_string(&statuses).unwrap(), r#"[1,2,7,null,-1,"premium"]"#);
+/// ```
+///
+/// Unexpected statuses and genders don't fail the whole [`User`]:
+///
+/// ```rust
+/// # use deezer::models::{Gender, User, UserStatus};
+/// let json = r#"{"id": 2529, "name": "dadbond", "status": -1, "gender": null, "link": "https://www.deezer.com/profile/2529",
+///     "picture": "", "picture_small": "", "picture_medium": "", "picture_big": "", "picture_xl": "", "country": "FR",
+///     "tracklist": "https://api.deezer.com/user/2529/flow"}"#;
+///
+/// let user: User = serde_json::from_str(json).unwrap();
+/// assert_eq!(user.status, UserStatus::Unknown((-1).into()));
+/// assert_eq!(user.gender, Gender::Unknown(String::new()));
+///
+/// let json = json.replace(r#""status": -1, "gender": null"#, r#""status": "gold", "gender": 3"#);
+/// let user: User = serde_json::from_str(&json).unwrap();
+/// assert_eq!(user.status, UserStatus::Unknown("gold".into()));
+/// assert_eq!(user.gender, Gender::Unknown("3".to_string()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum UserStatus {
+    /// A free account
+    #[default]
+    Freemium,
+
+    /// A premium subscription
+    Premium,
+
+    /// A premium+ subscription
+    PremiumPlus,
+
+    /// A status not known to this crate, holding the value sent by deezer
+    Unknown(serde_json::Value),
+}
+
+impl UserStatus {
+    /// Returns the numeric status as used by the api, `None` for unknown statuses which aren't a number.
+    pub fn code(&self) -> Option<u64> {
+        match self {
+            UserStatus::Freemium => Some(0),
+            UserStatus::Premium => Some(1),
+            UserStatus::PremiumPlus => Some(2),
+            UserStatus::Unknown(status) => status.as_u64(),
+        }
+    }
+}
+
+impl From<u64> for UserStatus {
+    fn from(status: u64) -> Self {
+        match status {
+            0 => UserStatus::Freemium,
+            1 => UserStatus::Premium,
+            2 => UserStatus::PremiumPlus,
+            status => UserStatus::Unknown(status.into()),
+        }
+    }
+}
+
+/// Statuses are also accepted as numeric strings, any other value is kept unchanged.
+impl<'de> Deserialize<'de> for UserStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let status = serde_json::Value::deserialize(deserializer)?;
+
+        Ok(match crate::serde_helpers::lenient_code(&status) {
+            Ok(Some(code @ 0..=2)) => UserStatus::from(code),
+            _ => UserStatus::Unknown(status),
+        })
+    }
+}
+
+impl Serialize for UserStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            UserStatus::Unknown(status) => status.serialize(serializer),
+            status => serializer.serialize_u64(status.code().expect("known statuses have a code")),
+        }
+    }
+}
+
+/// The gender of a [`User`].
+///
+/// Genders unknown to this crate, including the empty string deezer sends for users without a gender,
+/// are kept as [`Gender::Unknown`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::models::Gender;
+/// let genders: Vec<Gender> = serde_json::from_str(r#"["F", "m", "", 1, -1, null]"#).unwrap();
+///
+/// assert_eq!(genders, vec![
+///     Gender::Female,
+///     Gender::Male,
+///     Gender::Unknown(String::new()),
+///     Gender::Unknown("1".to_string()),
+///     Gender::Unknown("-1".to_string()),
+///     Gender::Unknown(String::new()),
+/// ]);
+/// assert_eq!(Gender::Female.as_str(), "F");
+/// ```
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(into = "String")]
+pub enum Gender {
+    /// `F`
+    Female,
+
+    /// `M`
+    Male,
+
+    /// A gender not known to this crate
+    Unknown(String),
+}
+
+impl Gender {
+    /// Returns the gender as used by the api.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Gender::Female => "F",
+            Gender::Male => "M",
+            Gender::Unknown(gender) => gender,
+        }
+    }
+}
+
+impl Default for Gender {
+    fn default() -> Self {
+        Gender::Unknown(String::new())
+    }
+}
+
+impl From<String> for Gender {
+    fn from(gender: String) -> Self {
+        match gender.as_str() {
+            "F" | "f" => Gender::Female,
+            "M" | "m" => Gender::Male,
+            _ => Gender::Unknown(gender),
+        }
+    }
+}
+
+impl From<Gender> for String {
+    fn from(gender: Gender) -> Self {
+        match gender {
+            Gender::Unknown(gender) => gender,
+            gender => gender.as_str().to_string(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Gender {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        crate::serde_helpers::lenient_string(deserializer).map(Gender::from)
+    }
+}
+
 impl DeezerObject for User {
-    fn get_api_url(id: u64) -> String {
+    type Id = UserId;
+
+    fn get_api_url(id: UserId) -> String {
         format!("user/{}", id)
     }
 }
+
+impl User {
+    /// Creates a user with the given id and name, all other fields are left empty.
+    pub fn new(id: impl Into<UserId>, name: impl Into<String>) -> Self {
+        User {
+            id: id.into(),
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the url of the picture in the given size.
+    pub fn image_url(&self, size: ImageSize) -> &str {
+        match size {
+            ImageSize::Small => &self.picture_small,
+            ImageSize::Medium => &self.picture_medium,
+            ImageSize::Big => &self.picture_big,
+            ImageSize::Xl => &self.picture_xl,
+        }
+    }
+
+    /// Fetches the user's flow from its [`track_list`](User::track_list).
+    ///
+    /// The flow is a personalized, endless mix, so each call returns the next tracks.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let user = deezer.user(2529).await?.unwrap();
+    /// let flow = user.fetch_flow(&deezer).await?;
+    /// # assert!(flow.len() <= 100);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_flow(&self, client: &DeezerClient) -> Result<Vec<PartialTrack>> {
+        client.get_list_from_url(&self.track_list, None, None).await
+    }
+
+    /// Returns the parsed [`birthday`](User::birthday).
+    ///
+    /// Returns `None` when the birthday is not available.
+    #[cfg(feature = "chrono")]
+    pub fn born_on(&self) -> Option<NaiveDate> {
+        crate::dates::parse_date(&self.birthday)
+    }
+
+    /// Returns the parsed [`inscription_date`](User::inscription_date).
+    ///
+    /// Returns `None` when the inscription date is not available.
+    #[cfg(feature = "chrono")]
+    pub fn inscribed_on(&self) -> Option<NaiveDate> {
+        crate::dates::parse_date(&self.inscription_date)
+    }
+}
+
+/// Subset of [`User`].
+///
+/// Embedded in other api objects, which fields are available depends on the embedding api.
+///
+/// Use [`get_full()`] for the full [`User`].
+///
+/// [`get_full()`]: PartialUser::get_full
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default, DeezerPartial)]
+#[deezer(full = User)]
+#[non_exhaustive]
+pub struct PartialUser {
+    /// The user's Deezer ID
+    pub id: UserId,
+
+    /// The user's Deezer nickname
+    pub name: String,
+
+    /// The url of the profil for the user on Deezer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+
+    /// The url of the user's profile picture.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub picture: Option<String>,
+
+    /// The url of the user's profile picture in size small.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub picture_small: Option<String>,
+
+    /// The url of the user's profile picture in size medium.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub picture_medium: Option<String>,
+
+    /// The url of the user's profile picture in size big.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub picture_big: Option<String>,
+
+    /// The url of the user's profile picture in size xl.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub picture_xl: Option<String>,
+
+    /// API Link to the flow of this user
+    #[serde(rename = "tracklist", default, skip_serializing_if = "Option::is_none")]
+    pub track_list: Option<String>,
+}
+
+impl PartialUser {
+    /// Creates a user with the given id and name, all other fields are left empty.
+    pub fn new(id: impl Into<UserId>, name: impl Into<String>) -> Self {
+        PartialUser {
+            id: id.into(),
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the url of the picture in the given size.
+    ///
+    /// Returns `None` when the embedding api doesn't provide this size.
+    pub fn image_url(&self, size: ImageSize) -> Option<&str> {
+        match size {
+            ImageSize::Small => self.picture_small.as_deref(),
+            ImageSize::Medium => self.picture_medium.as_deref(),
+            ImageSize::Big => self.picture_big.as_deref(),
+            ImageSize::Xl => self.picture_xl.as_deref(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::models::tests::odd_codes;
+
+    #[test]
+    fn statuses_keep_unknown_values() {
+        for code in odd_codes() {
+            let status: UserStatus = serde_json::from_value(code.clone()).unwrap();
+            let serialized = serde_json::to_value(&status).unwrap();
+
+            match &status {
+                UserStatus::Unknown(unknown) => {
+                    assert_eq!(unknown, &code);
+                    assert_eq!(serialized, code);
+                }
+                known => assert_eq!(serialized, json!(known.code().unwrap())),
+            }
+            assert_eq!(serde_json::from_value::<UserStatus>(serialized).unwrap(), status);
+        }
+    }
+
+    #[test]
+    fn numeric_strings_are_known_statuses() {
+        assert_eq!(serde_json::from_value::<UserStatus>(json!(" 1 ")).unwrap(), UserStatus::Premium);
+        assert_eq!(serde_json::from_value::<UserStatus>(json!(3)).unwrap(), UserStatus::Unknown(json!(3)));
+    }
+
+    #[test]
+    fn users_with_odd_statuses_and_genders_deserialize() {
+        let mut user = serde_json::to_value(User::new(2529, "dadbond")).unwrap();
+        for code in odd_codes() {
+            user["status"] = code.clone();
+            // genders are strings, only scalars are expected in their place
+            if !matches!(code, Value::Array(_) | Value::Object(_)) {
+                user["gender"] = code.clone();
+            }
+
+            let parsed: User = serde_json::from_value(user.clone()).unwrap();
+            assert_eq!(parsed.status, UserStatus::deserialize(&code).unwrap());
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Conversion of tracks into [MPRIS](https://specifications.freedesktop.org/mpris-spec/latest/) metadata.
+//!
+//! The metadata is returned as plain values, so it can be handed to any dbus implementation.
+#![warn(missing_docs)]
+use std::collections::BTreeMap;
+
+use crate::models::{ImageSize, Track};
+
+/// A value of the MPRIS metadata map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetadataValue {
+    /// A dbus object path (`o`)
+    ObjectPath(String),
+
+    /// A string (`s`)
+    String(String),
+
+    /// A list of strings (`as`)
+    Strings(Vec<String>),
+
+    /// A 32 bit integer (`i`)
+    I32(i32),
+
+    /// A 64 bit integer (`x`)
+    I64(i64),
+}
+
+/// Returns the MPRIS metadata of the given track.
+///
+/// The track id is exported as `/com/deezer/track/<id>`, the length in microseconds.
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::models::*;
+/// # use deezer::mpris::{self, MetadataValue};
+/// let mut track = Track::new(3135556, "Harder, Better, Faster, Stronger");
+/// track.artist = TrackArtist::new(27, "Daft Punk");
+/// track.duration_in_seconds = 224;
+///
+/// let metadata = mpris::track_metadata(&track);
+/// assert_eq!(metadata["xesam:title"], MetadataValue::String(track.title.clone()));
+/// assert_eq!(metadata["xesam:artist"], MetadataValue::Strings(vec!["Daft Punk".to_string()]));
+/// assert_eq!(metadata["mpris:length"], MetadataValue::I64(224_000_000));
+/// ```
+pub fn track_metadata(track: &Track) -> BTreeMap<&'static str, MetadataValue> {
+    let mut metadata = BTreeMap::new();
+    metadata.insert("mpris:trackid", MetadataValue::ObjectPath(format!("/com/deezer/track/{}", track.id)));
+    metadata.insert("mpris:length", MetadataValue::I64(track.duration().as_micros() as i64));
+    metadata.insert("xesam:title", MetadataValue::String(track.title.clone()));
+    metadata.insert("xesam:artist", MetadataValue::Strings(vec![track.artist.name.clone()]));
+    metadata.insert("xesam:album", MetadataValue::String(track.album.title.clone()));
+    metadata.insert("xesam:url", MetadataValue::String(track.link.clone()));
+    if track.track_position_in_album > 0 {
+        metadata.insert("xesam:trackNumber", MetadataValue::I32(track.track_position_in_album as i32));
+    }
+    if track.album_disk_number > 0 {
+        metadata.insert("xesam:discNumber", MetadataValue::I32(track.album_disk_number as i32));
+    }
+    if let Some(art_url) = track.album.image_url(ImageSize::Xl) {
+        metadata.insert("mpris:artUrl", MetadataValue::String(art_url.to_string()));
+    }
+
+    metadata
+}
@@ -0,0 +1,42 @@
+//! Tracing spans of api requests following the OpenTelemetry semantic conventions for http clients.
+//!
+//! The span fields are picked up as attributes by `tracing-opentelemetry`.
+use reqwest::{Request, Response};
+use tracing::field::Empty;
+use tracing::Instrument;
+
+/// Sends `request` inside a client span, recording the status code of the response.
+pub(crate) async fn send_instrumented(client: &reqwest::Client, request: Request,
+                                      resend_count: u32) -> reqwest::Result<Response> {
+    let span = tracing::info_span!(
+        "deezer.request",
+        otel.kind = "client",
+        otel.name = %request.method(),
+        otel.status_code = Empty,
+        http.request.method = %request.method(),
+        server.address = request.url().host_str().unwrap_or_default(),
+        url.path = request.url().path(),
+        http.request.resend_count = Empty,
+        http.response.status_code = Empty,
+        error.type = Empty,
+    );
+    if resend_count > 0 {
+        span.record("http.request.resend_count", resend_count);
+    }
+    let response = client.execute(request).instrument(span.clone()).await;
+    match &response {
+        Ok(response) => {
+            span.record("http.response.status_code", response.status().as_u16());
+            if response.status().is_client_error() || response.status().is_server_error() {
+                span.record("otel.status_code", "ERROR");
+                span.record("error.type", response.status().as_str());
+            }
+        }
+        Err(err) => {
+            span.record("otel.status_code", "ERROR");
+            span.record("error.type", tracing::field::display(err));
+        }
+    }
+
+    response
+}
@@ -0,0 +1,109 @@
+//! Query parameters of api requests
+#![warn(missing_docs)]
+use std::borrow::Cow;
+
+use serde::Serialize;
+
+use crate::models::image::ImageSize;
+use crate::models::{AlbumId, ArtistId, PlaylistId, TrackId, UserId};
+
+/// A value which can be passed as query parameter.
+pub trait ParamValue {
+    /// Returns the value as it's sent to the api
+    fn to_param(&self) -> Cow<'_, str>;
+}
+
+impl ParamValue for str {
+    fn to_param(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl ParamValue for String {
+    fn to_param(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl ParamValue for bool {
+    fn to_param(&self) -> Cow<'_, str> {
+        Cow::Borrowed(if *self { "true" } else { "false" })
+    }
+}
+
+impl<T: ParamValue + ?Sized> ParamValue for &T {
+    fn to_param(&self) -> Cow<'_, str> {
+        (**self).to_param()
+    }
+}
+
+macro_rules! display_param_value {
+    ($($value:ty),* $(,)?) => {
+        $(
+            impl ParamValue for $value {
+                fn to_param(&self) -> Cow<'_, str> {
+                    Cow::Owned(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+display_param_value!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, ImageSize, AlbumId, ArtistId, PlaylistId, TrackId, UserId);
+
+/// Query parameters of a request.
+///
+/// Keys are static, the values are converted once when they are added.
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::Params;
+/// let params = Params::new()
+///     .with("limit", 25u32)
+///     .with("strict", true)
+///     .with_optional("index", None::<u32>);
+///
+/// assert_eq!(params.iter().collect::<Vec<_>>(), vec![("limit", "25"), ("strict", "true")]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct Params {
+    pairs: Vec<(&'static str, String)>,
+}
+
+impl Params {
+    /// Creates an empty parameter list
+    pub fn new() -> Self {
+        Params::default()
+    }
+
+    /// Adds a parameter
+    pub fn insert(&mut self, key: &'static str, value: impl ParamValue) {
+        self.pairs.push((key, value.to_param().into_owned()));
+    }
+
+    /// Adds a parameter and returns the parameter list
+    pub fn with(mut self, key: &'static str, value: impl ParamValue) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    /// Adds a parameter when a value is given
+    pub fn with_optional(self, key: &'static str, value: Option<impl ParamValue>) -> Self {
+        match value {
+            Some(value) => self.with(key, value),
+            None => self,
+        }
+    }
+
+    /// Returns `true` when there are no parameters
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Returns an iterator over the parameters
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        self.pairs.iter().map(|(key, value)| (*key, value.as_str()))
+    }
+}
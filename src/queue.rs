@@ -0,0 +1,152 @@
+//! Prioritised execution of api requests
+#![warn(missing_docs)]
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use futures::channel::oneshot;
+
+/// Priority of a request in a [`FetchQueue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Priority {
+    /// Bulk work like a library sync, which may wait
+    Background,
+    /// Lookups a user is waiting for
+    Interactive,
+}
+
+/// Limits the number of requests in flight, starting waiting requests by priority.
+///
+/// Requests of the same priority are started in the order they were enqueued.
+/// Combined with a [`RateLimiter`](crate::rate_limit::RateLimiter) on the client this keeps
+/// interactive lookups responsive while a bulk job is draining the rate limit.
+///
+/// # Examples
+/// ```rust
+/// # use deezer::{DeezerClient, DeezerError};
+/// # use deezer::queue::{FetchQueue, Priority};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), DeezerError> {
+/// let deezer = DeezerClient::new();
+/// let queue = FetchQueue::new(2);
+///
+/// let album = queue.enqueue(Priority::Interactive, deezer.album(302127)).await?;
+/// # assert!(album.is_some());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FetchQueue {
+    state: Arc<Mutex<QueueState>>,
+}
+
+#[derive(Debug)]
+struct QueueState {
+    concurrency: usize,
+    in_flight: usize,
+    enqueued: u64,
+    waiting: BinaryHeap<Waiter>,
+}
+
+#[derive(Debug)]
+struct Waiter {
+    priority: Priority,
+    sequence: u64,
+    start: oneshot::Sender<Permit>,
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Waiter {}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Waiter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // the heap pops the greatest waiter first: highest priority, then lowest sequence
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+/// A slot of a request in flight, it's passed to the next waiter when dropped.
+#[derive(Debug)]
+struct Permit {
+    state: Arc<Mutex<QueueState>>,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap();
+        while let Some(waiter) = state.waiting.pop() {
+            let permit = Permit {
+                state: Arc::clone(&self.state),
+            };
+            match waiter.start.send(permit) {
+                Ok(()) => return,
+                // the waiter was cancelled, don't release the slot twice
+                Err(permit) => std::mem::forget(permit),
+            }
+        }
+        state.in_flight -= 1;
+    }
+}
+
+impl FetchQueue {
+    /// Creates a queue running at most `concurrency` requests at once.
+    pub fn new(concurrency: usize) -> Self {
+        FetchQueue {
+            state: Arc::new(Mutex::new(QueueState {
+                concurrency: concurrency.max(1),
+                in_flight: 0,
+                enqueued: 0,
+                waiting: BinaryHeap::new(),
+            })),
+        }
+    }
+
+    /// Runs `fetch` once all requests of higher priority have been started and a slot is free.
+    pub async fn enqueue<F: Future>(&self, priority: Priority, fetch: F) -> F::Output {
+        let _permit = self.acquire(priority).await;
+
+        fetch.await
+    }
+
+    /// Returns the number of requests waiting for a free slot.
+    pub fn waiting(&self) -> usize {
+        self.state.lock().unwrap().waiting.len()
+    }
+
+    async fn acquire(&self, priority: Priority) -> Permit {
+        let start = {
+            let mut state = self.state.lock().unwrap();
+            if state.in_flight < state.concurrency {
+                state.in_flight += 1;
+                return Permit {
+                    state: Arc::clone(&self.state),
+                };
+            }
+            let (sender, receiver) = oneshot::channel();
+            state.enqueued += 1;
+            let sequence = state.enqueued;
+            state.waiting.push(Waiter {
+                priority,
+                sequence,
+                start: sender,
+            });
+            receiver
+        };
+
+        start.await.expect("waiting requests are started before the queue is dropped")
+    }
+}
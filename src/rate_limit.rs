@@ -0,0 +1,105 @@
+//! Client side rate limiting of api requests
+#![warn(missing_docs)]
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::{sleep_until, Instant};
+
+/// Spaces requests evenly, allowing at most the configured number of requests per interval.
+///
+/// The limiter can be shared between tasks, each caller of [`acquire()`](RateLimiter::acquire)
+/// is assigned the next free slot.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// # use deezer::rate_limit::RateLimiter;
+/// # #[tokio::main]
+/// # async fn main() {
+/// // Deezer allows 50 requests per 5 seconds
+/// let limiter = RateLimiter::new(50, Duration::from_secs(5));
+///
+/// limiter.acquire().await;
+/// // issue request
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    spacing: Duration,
+    next_slot: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter allowing `requests` requests per `interval`.
+    pub fn new(requests: u32, interval: Duration) -> Self {
+        RateLimiter {
+            spacing: interval / requests.max(1),
+            next_slot: Mutex::new(None),
+        }
+    }
+
+    /// Waits until the next request may be issued.
+    pub async fn acquire(&self) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().unwrap();
+            let now = Instant::now();
+            let slot = next_slot.map_or(now, |next| next.max(now));
+            *next_slot = Some(slot + self.spacing);
+            slot
+        };
+        sleep_until(slot).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn requests_are_spaced_evenly() {
+        let limiter = RateLimiter::new(5, Duration::from_secs(1));
+        let start = Instant::now();
+
+        let mut elapsed = Vec::new();
+        for _ in 0..4 {
+            limiter.acquire().await;
+            elapsed.push(start.elapsed());
+        }
+
+        assert_eq!(elapsed, vec![
+            Duration::ZERO,
+            Duration::from_millis(200),
+            Duration::from_millis(400),
+            Duration::from_millis(600),
+        ]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn idle_time_is_not_saved_up() {
+        let limiter = RateLimiter::new(5, Duration::from_secs(1));
+        limiter.acquire().await;
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        let start = Instant::now();
+
+        limiter.acquire().await;
+        limiter.acquire().await;
+
+        assert_eq!(start.elapsed(), Duration::from_millis(200));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn concurrent_callers_get_separate_slots() {
+        let limiter = RateLimiter::new(10, Duration::from_secs(1));
+        let start = Instant::now();
+
+        futures::join!(limiter.acquire(), limiter.acquire(), limiter.acquire());
+
+        assert_eq!(start.elapsed(), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn zero_requests_are_treated_as_one() {
+        assert_eq!(RateLimiter::new(0, Duration::from_secs(1)).spacing, Duration::from_secs(1));
+    }
+}
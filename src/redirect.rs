@@ -0,0 +1,82 @@
+//! Redirect handling of the requests issued by the [`DeezerClient`](crate::DeezerClient)
+//!
+//! Preview and image urls of the api may redirect through CDNs before serving the content.
+#![warn(missing_docs)]
+use reqwest::redirect::{Attempt, Policy};
+
+/// Default number of redirects followed, same as reqwest's default
+const DEFAULT_MAX_REDIRECTS: usize = 10;
+
+/// Controls which redirects are followed, see [`DeezerClient::with_redirect_policy()`](crate::DeezerClient::with_redirect_policy).
+///
+/// Requests failing due to the policy return a [`DeezerError::HttpError`](crate::DeezerError::HttpError).
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::DeezerClient;
+/// # use deezer::redirect::RedirectPolicy;
+/// let deezer = DeezerClient::new().with_redirect_policy(RedirectPolicy::new().max_redirects(3).same_host_only());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RedirectPolicy {
+    max_redirects: usize,
+    cross_host: bool,
+}
+
+impl Default for RedirectPolicy {
+    fn default() -> Self {
+        RedirectPolicy::new()
+    }
+}
+
+impl RedirectPolicy {
+    /// Follows up to 10 redirects to any host.
+    pub fn new() -> Self {
+        RedirectPolicy {
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            cross_host: true,
+        }
+    }
+
+    /// Doesn't follow any redirects, the redirect response is returned instead.
+    pub fn none() -> Self {
+        RedirectPolicy::new().max_redirects(0)
+    }
+
+    /// Fails requests redirected more than `max` times.
+    pub fn max_redirects(mut self, max: usize) -> Self {
+        self.max_redirects = max;
+        self
+    }
+
+    /// Fails requests redirected to a different host than the one originally requested.
+    pub fn same_host_only(mut self) -> Self {
+        self.cross_host = false;
+        self
+    }
+
+    fn check(&self, attempt: Attempt) -> reqwest::redirect::Action {
+        if attempt.previous().len() > self.max_redirects {
+            return attempt.error(format!("too many redirects (max {})", self.max_redirects));
+        }
+        if !self.cross_host {
+            let origin = attempt.previous().first().and_then(|url| url.host_str());
+            let target = attempt.url().host_str();
+            if origin != target {
+                let error = format!("redirect to other host {}", target.unwrap_or_default());
+                return attempt.error(error);
+            }
+        }
+
+        attempt.follow()
+    }
+
+    pub(crate) fn into_policy(self) -> Policy {
+        if self.max_redirects == 0 {
+            return Policy::none();
+        }
+
+        Policy::custom(move |attempt| self.check(attempt))
+    }
+}
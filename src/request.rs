@@ -0,0 +1,60 @@
+//! Requests to api endpoints which are not modelled by this crate
+#![warn(missing_docs)]
+use std::fmt::Display;
+
+use reqwest::header::{HeaderName, HeaderValue};
+use serde::de::DeserializeOwned;
+
+use crate::{DeezerClient, Params, Result};
+
+/// Builder of a raw api request, created by [`DeezerClient::request()`].
+#[derive(Debug)]
+pub struct RequestBuilder<'a> {
+    client: &'a DeezerClient,
+    request: reqwest::RequestBuilder,
+}
+
+impl<'a> RequestBuilder<'a> {
+    pub(crate) fn new(client: &'a DeezerClient, request: reqwest::RequestBuilder) -> Self {
+        RequestBuilder { client, request }
+    }
+
+    /// Appends a query parameter.
+    pub fn query(mut self, key: &str, value: impl Display) -> Self {
+        self.request = self.request.query(&[(key, value.to_string())]);
+        self
+    }
+
+    /// Appends all given query parameters.
+    pub fn params(mut self, params: &Params) -> Self {
+        self.request = self.request.query(params);
+        self
+    }
+
+    /// Authenticates the request with the given OAuth access token.
+    ///
+    /// The token is sent instead of the one of the client's [token store](DeezerClient::with_token_store).
+    pub fn access_token(self, token: &str) -> Self {
+        self.query("access_token", token)
+    }
+
+    /// Adds a header.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.request = self.request.header(name, value);
+        self
+    }
+
+    /// Sends the request, discarding the response body.
+    pub async fn send(self) -> Result<()> {
+        self.client.execute_api(self.request).await?;
+
+        Ok(())
+    }
+
+    /// Sends the request and parses the response body.
+    pub async fn json<T: DeserializeOwned>(self) -> Result<T> {
+        let body = self.client.execute_api(self.request).await?;
+
+        Ok(serde_json::from_slice(&body)?)
+    }
+}
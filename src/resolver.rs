@@ -0,0 +1,186 @@
+//! Bulk resolution of foreign catalog identifiers to Deezer objects.
+//!
+//! Tools migrating libraries from other services usually know the ISRCs of tracks and the UPCs
+//! of albums. [`CatalogResolver`] maps those to Deezer objects while staying below the api's rate limit
+//! and remembering previous lookups.
+#![warn(missing_docs)]
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::fetch::{self, BatchResult};
+use crate::models::{Album, Isrc, Track, Upc};
+use crate::rate_limit::RateLimiter;
+use crate::{DeezerClient, Result};
+
+/// Resolves ISRCs to [`Track`]s and UPCs to [`Album`]s.
+///
+/// Lookups, including those without a result, are cached for the lifetime of the resolver.
+///
+/// # Examples
+/// ```rust
+/// # use deezer::{DeezerClient, DeezerError};
+/// # use deezer::resolver::CatalogResolver;
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), DeezerError> {
+/// let resolver = CatalogResolver::new(DeezerClient::new());
+/// let tracks = resolver.tracks_by_isrc(vec!["GBDUW0000059".to_string()]).await;
+/// assert_eq!(tracks.succeeded.len() + tracks.failed.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct CatalogResolver {
+    client: DeezerClient,
+    concurrency: usize,
+    limiter: RateLimiter,
+    tracks: Mutex<HashMap<Isrc, Option<Track>>>,
+    albums: Mutex<HashMap<Upc, Option<Album>>>,
+}
+
+impl CatalogResolver {
+    /// Creates a resolver issuing up to 4 concurrent requests and at most 50 requests per 5 seconds.
+    pub fn new(client: DeezerClient) -> Self {
+        CatalogResolver::with_limits(client, 4, RateLimiter::new(50, Duration::from_secs(5)))
+    }
+
+    /// Creates a resolver with a custom concurrency and rate limit.
+    pub fn with_limits(client: DeezerClient, concurrency: usize, limiter: RateLimiter) -> Self {
+        CatalogResolver {
+            client,
+            concurrency,
+            limiter,
+            tracks: Mutex::default(),
+            albums: Mutex::default(),
+        }
+    }
+
+    /// Resolves the isrcs to tracks, in the order of `isrcs`.
+    ///
+    /// Each resolved isrc is returned together with its track, which is `None` for isrcs without a Deezer track.
+    /// Failed lookups are collected in [`BatchResult::failed`] instead of aborting the whole batch,
+    /// like [`albums_by_upc()`](CatalogResolver::albums_by_upc).
+    pub async fn tracks_by_isrc(&self, isrcs: impl IntoIterator<Item = Isrc>) -> BatchResult<(Isrc, Option<Track>), Isrc> {
+        fetch::settled(isrcs, self.concurrency, |isrc| async move {
+            let track = self.cached(&self.tracks, isrc.clone(), |isrc| self.client.track_by_isrc(isrc)).await?;
+
+            Ok((isrc, track))
+        })
+        .await
+    }
+
+    /// Resolves the upcs to albums, in the order of `upcs`, e.g. distributor provided upc lists.
+    ///
+    /// Each resolved upc is returned together with its album, which is `None` for upcs without a Deezer album.
+    /// Failed lookups are collected in [`BatchResult::failed`] instead of aborting the whole batch.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # use deezer::resolver::CatalogResolver;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let resolver = CatalogResolver::new(DeezerClient::new());
+    /// let result = resolver.albums_by_upc(vec!["724384960650".to_string()]).await;
+    /// for (upc, album) in result.succeeded {
+    ///     match album {
+    ///         Some(album) => println!("{} is {}", upc, album.title),
+    ///         None => println!("{} is not on Deezer", upc),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn albums_by_upc(&self, upcs: impl IntoIterator<Item = Upc>) -> BatchResult<(Upc, Option<Album>), Upc> {
+        fetch::settled(upcs, self.concurrency, |upc| async move {
+            let album = self.cached(&self.albums, upc.clone(), |upc| self.client.album_by_upc(upc)).await?;
+
+            Ok((upc, album))
+        })
+        .await
+    }
+
+    async fn cached<K, V, F, Fut>(&self, cache: &Mutex<HashMap<K, Option<V>>>, key: K, fetch: F) -> Result<Option<V>>
+    where
+        K: Hash + Eq + Clone,
+        V: Clone,
+        F: FnOnce(K) -> Fut,
+        Fut: std::future::Future<Output = Result<Option<V>>>,
+    {
+        if let Some(value) = cache.lock().unwrap().get(&key) {
+            return Ok(value.clone());
+        }
+        self.limiter.acquire().await;
+        let value = fetch(key.clone()).await?;
+        cache.lock().unwrap().insert(key, value.clone());
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::MockServer;
+
+    const NO_DATA: &str = r#"{"error": {"type": "DataException", "message": "no data", "code": 800}}"#;
+    const QUOTA: &str = r#"{"error": {"type": "Exception", "message": "Quota limit exceeded", "code": 4}}"#;
+
+    fn fail_lookups(server: &MockServer) {
+        server.respond("/track/isrc:FAILING", QUOTA).respond("/album/upc:FAILING", QUOTA);
+    }
+
+    fn resolver(server: &MockServer) -> CatalogResolver {
+        let client = DeezerClient::new().with_base_url(server.url());
+
+        CatalogResolver::with_limits(client, 2, RateLimiter::new(1000, Duration::from_secs(1)))
+    }
+
+    #[tokio::test]
+    async fn isrcs_and_upcs_are_resolved_per_item() {
+        let server = MockServer::start().await;
+        server
+            .respond("/track/isrc:GBDUW0000059", serde_json::to_string(&Track::new(3135556, "Harder, Better, Faster, Stronger")).unwrap())
+            .respond("/track/isrc:UNKNOWN", NO_DATA)
+            .respond("/album/upc:724384960650", serde_json::to_string(&Album::new(302127, "Discovery")).unwrap())
+            .respond("/album/upc:UNKNOWN", NO_DATA);
+        fail_lookups(&server);
+        let resolver = resolver(&server);
+
+        let tracks = resolver.tracks_by_isrc(vec!["GBDUW0000059".to_string(), "UNKNOWN".to_string(), "FAILING".to_string()]).await;
+        let albums = resolver.albums_by_upc(vec!["724384960650".to_string(), "UNKNOWN".to_string(), "FAILING".to_string()]).await;
+
+        let tracks: Vec<_> = tracks.succeeded.into_iter().map(|(isrc, track)| (isrc, track.map(|track| track.id.get()))).collect();
+        assert_eq!(tracks, vec![("GBDUW0000059".to_string(), Some(3135556)), ("UNKNOWN".to_string(), None)]);
+        let albums: Vec<_> = albums.succeeded.into_iter().map(|(upc, album)| (upc, album.map(|album| album.id.get()))).collect();
+        assert_eq!(albums, vec![("724384960650".to_string(), Some(302127)), ("UNKNOWN".to_string(), None)]);
+    }
+
+    #[tokio::test]
+    async fn failed_lookups_are_reported_and_not_cached() {
+        let server = MockServer::start().await;
+        fail_lookups(&server);
+        let resolver = resolver(&server);
+
+        let tracks = resolver.tracks_by_isrc(vec!["FAILING".to_string()]).await;
+        let albums = resolver.albums_by_upc(vec!["FAILING".to_string()]).await;
+        resolver.tracks_by_isrc(vec!["FAILING".to_string()]).await;
+
+        assert_eq!(tracks.failed.iter().map(|(isrc, _)| isrc.as_str()).collect::<Vec<_>>(), vec!["FAILING"]);
+        assert_eq!(albums.failed.iter().map(|(upc, _)| upc.as_str()).collect::<Vec<_>>(), vec!["FAILING"]);
+        assert_eq!(server.requests_to("/track/isrc:FAILING").len(), 2);
+    }
+
+    #[tokio::test]
+    async fn lookups_without_result_are_cached() {
+        let server = MockServer::start().await;
+        server.respond("/track/isrc:UNKNOWN", NO_DATA);
+        let resolver = resolver(&server);
+
+        resolver.tracks_by_isrc(vec!["UNKNOWN".to_string()]).await;
+        resolver.tracks_by_isrc(vec!["UNKNOWN".to_string()]).await;
+
+        assert_eq!(server.requests_to("/track/isrc:UNKNOWN").len(), 1);
+    }
+}
@@ -0,0 +1,235 @@
+//! The advanced search syntax of the api
+//!
+//! Searches can be narrowed with filters like `artist:"aloe blacc" dur_min:120`,
+//! see the [Deezer Api Documentation](https://developers.deezer.com/api/search).
+#![warn(missing_docs)]
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+/// A parsed search query with its filters.
+///
+/// Parsing validates the filters, the [`Display`](fmt::Display) implementation returns the normalized query.
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::search::SearchQuery;
+/// let query = SearchQuery::parse("artist:'aloe blacc'  dur_min:120 loudness").unwrap();
+///
+/// assert_eq!(query.artist.as_deref(), Some("aloe blacc"));
+/// assert_eq!(query.dur_min, Some(120));
+/// assert_eq!(query.text, "loudness");
+/// assert_eq!(query.to_string(), r#"loudness artist:"aloe blacc" dur_min:120"#);
+/// ```
+///
+/// Invalid filters are rejected:
+///
+/// ```rust
+/// # use deezer::search::{SearchQuery, SearchQueryError};
+/// assert!(matches!(SearchQuery::parse("bpm_min:fast"), Err(SearchQueryError::InvalidNumber { .. })));
+/// assert!(matches!(SearchQuery::parse("genre:pop"), Err(SearchQueryError::UnknownFilter(_))));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct SearchQuery {
+    /// The search terms outside of filters
+    pub text: String,
+
+    /// Only matches tracks of the artist
+    pub artist: Option<String>,
+
+    /// Only matches tracks of the album
+    pub album: Option<String>,
+
+    /// Only matches tracks with the title
+    pub track: Option<String>,
+
+    /// Only matches tracks released by the label
+    pub label: Option<String>,
+
+    /// Minimum duration in seconds
+    pub dur_min: Option<u32>,
+
+    /// Maximum duration in seconds
+    pub dur_max: Option<u32>,
+
+    /// Minimum beats per minute
+    pub bpm_min: Option<u32>,
+
+    /// Maximum beats per minute
+    pub bpm_max: Option<u32>,
+}
+
+/// Reasons a [`SearchQuery`] could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SearchQueryError {
+    /// The filter is not supported by the api
+    #[error("unknown search filter `{0}`")]
+    UnknownFilter(String),
+
+    /// A filter has no value
+    #[error("search filter `{0}` has no value")]
+    MissingValue(String),
+
+    /// A numeric filter has a value which is not a number
+    #[error("search filter `{filter}` expects a number, got `{value}`")]
+    InvalidNumber {
+        /// The name of the filter
+        filter: String,
+        /// The invalid value
+        value: String,
+    },
+
+    /// The minimum of a range is greater than its maximum
+    #[error("search filter `{0}_min` is greater than `{0}_max`")]
+    InvalidRange(&'static str),
+
+    /// A quote is not closed
+    #[error("unterminated quote in search query")]
+    UnterminatedQuote,
+}
+
+impl SearchQuery {
+    /// Creates a query searching for the given terms without filters.
+    pub fn new(text: impl Into<String>) -> Self {
+        SearchQuery {
+            text: text.into(),
+            ..SearchQuery::default()
+        }
+    }
+
+    /// Parses a query in the advanced search syntax.
+    pub fn parse(query: &str) -> Result<Self, SearchQueryError> {
+        query.parse()
+    }
+
+    /// Returns `true` when any filter is set.
+    pub fn has_filters(&self) -> bool {
+        self.artist.is_some()
+            || self.album.is_some()
+            || self.track.is_some()
+            || self.label.is_some()
+            || self.dur_min.is_some()
+            || self.dur_max.is_some()
+            || self.bpm_min.is_some()
+            || self.bpm_max.is_some()
+    }
+
+    fn set_filter(&mut self, filter: &str, value: String) -> Result<(), SearchQueryError> {
+        if value.is_empty() {
+            return Err(SearchQueryError::MissingValue(filter.to_string()));
+        }
+        let number = || {
+            value.parse().map_err(|_| SearchQueryError::InvalidNumber {
+                filter: filter.to_string(),
+                value: value.clone(),
+            })
+        };
+        match filter {
+            "artist" => self.artist = Some(value),
+            "album" => self.album = Some(value),
+            "track" => self.track = Some(value),
+            "label" => self.label = Some(value),
+            "dur_min" => self.dur_min = Some(number()?),
+            "dur_max" => self.dur_max = Some(number()?),
+            "bpm_min" => self.bpm_min = Some(number()?),
+            "bpm_max" => self.bpm_max = Some(number()?),
+            _ => return Err(SearchQueryError::UnknownFilter(filter.to_string())),
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for SearchQuery {
+    type Err = SearchQueryError;
+
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        let mut search = SearchQuery::default();
+        let mut terms = Vec::new();
+        for token in tokenize(query)? {
+            match token.split_once(':') {
+                Some((filter, value)) if is_filter_name(filter) => {
+                    search.set_filter(filter, value.to_string())?
+                }
+                _ => terms.push(token),
+            }
+        }
+        search.text = terms.join(" ");
+        if search.dur_min > search.dur_max && search.dur_max.is_some() {
+            return Err(SearchQueryError::InvalidRange("dur"));
+        }
+        if search.bpm_min > search.bpm_max && search.bpm_max.is_some() {
+            return Err(SearchQueryError::InvalidRange("bpm"));
+        }
+
+        Ok(search)
+    }
+}
+
+impl fmt::Display for SearchQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if !self.text.is_empty() {
+            parts.push(self.text.clone());
+        }
+        let texts = [
+            ("artist", &self.artist),
+            ("album", &self.album),
+            ("track", &self.track),
+            ("label", &self.label),
+        ];
+        for (filter, value) in texts.iter() {
+            if let Some(value) = value {
+                parts.push(format!("{}:\"{}\"", filter, value));
+            }
+        }
+        let numbers = [
+            ("dur_min", self.dur_min),
+            ("dur_max", self.dur_max),
+            ("bpm_min", self.bpm_min),
+            ("bpm_max", self.bpm_max),
+        ];
+        for (filter, value) in numbers.iter() {
+            if let Some(value) = value {
+                parts.push(format!("{}:{}", filter, value));
+            }
+        }
+
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+fn is_filter_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c == '_')
+}
+
+/// Splits the query at whitespace, keeping quoted values together and removing the quotes.
+fn tokenize(query: &str) -> Result<Vec<String>, SearchQueryError> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut quote = None;
+    for c in query.chars() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => token.push(c),
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c.is_whitespace() => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            None => token.push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err(SearchQueryError::UnterminatedQuote);
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
@@ -0,0 +1,327 @@
+//! Serde helpers for the conventions of the api payloads
+//!
+//! The models of this crate use these helpers, they can also be used for own structs parsing
+//! responses of endpoints which aren't modelled, see [`DeezerClient::request()`](crate::DeezerClient::request).
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use std::time::{Duration, UNIX_EPOCH, SystemTime};
+//! # use serde::Deserialize;
+//! # use deezer::serde_helpers;
+//! #[derive(Deserialize)]
+//! struct Entry {
+//!     #[serde(deserialize_with = "serde_helpers::id")]
+//!     id: u64,
+//!     #[serde(with = "serde_helpers::timestamp")]
+//!     added: SystemTime,
+//!     #[serde(default, deserialize_with = "serde_helpers::empty_string_as_none")]
+//!     comment: Option<String>,
+//! }
+//!
+//! let entry: Entry = serde_json::from_str(r#"{"id": "3135556", "added": 1600000000, "comment": ""}"#).unwrap();
+//!
+//! assert_eq!(entry.id, 3135556);
+//! assert_eq!(entry.added, UNIX_EPOCH + Duration::from_secs(1600000000));
+//! assert_eq!(entry.comment, None);
+//! ```
+#![warn(missing_docs)]
+use std::convert::TryFrom;
+use std::fmt;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::Deserialize;
+
+/// Deserializes an id which is sent either as number or as string.
+///
+/// Most payloads contain numeric ids, but some (e.g. the parents of comments) quote them.
+pub fn id<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(IdVisitor)
+}
+
+struct IdVisitor;
+
+impl<'de> Visitor<'de> for IdVisitor {
+    type Value = u64;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an id as number or string")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<u64, E> {
+        Ok(value)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<u64, E> {
+        u64::try_from(value).map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<u64, E> {
+        if value >= 0.0 && value.fract() == 0.0 && value <= u64::MAX as f64 {
+            Ok(value as u64)
+        } else {
+            Err(E::invalid_value(de::Unexpected::Float(value), &self))
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<u64, E> {
+        value
+            .trim()
+            .parse()
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+}
+
+/// Deserializes a string, also accepting numbers and bools as well as `null` as empty string.
+///
+/// Used by the enums of the models, so values of an unexpected type end up in their `Unknown` variant
+/// instead of failing the whole payload.
+///
+/// ```rust
+/// # use serde::Deserialize;
+/// # use deezer::serde_helpers;
+/// #[derive(Deserialize)]
+/// struct Entry {
+///     #[serde(deserialize_with = "serde_helpers::lenient_string")]
+///     kind: String,
+/// }
+///
+/// let entries: Vec<Entry> = serde_json::from_str(r#"[{"kind": "album"}, {"kind": 42}, {"kind": null}]"#).unwrap();
+///
+/// assert_eq!(entries[0].kind, "album");
+/// assert_eq!(entries[1].kind, "42");
+/// assert_eq!(entries[2].kind, "");
+/// ```
+pub fn lenient_string<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(LenientStringVisitor)
+}
+
+struct LenientStringVisitor;
+
+impl<'de> Visitor<'de> for LenientStringVisitor {
+    type Value = String;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string, number or bool")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<String, E> {
+        Ok(value.to_string())
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<String, E> {
+        Ok(value)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<String, E> {
+        Ok(value.to_string())
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<String, E> {
+        Ok(value.to_string())
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<String, E> {
+        Ok(value.to_string())
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<String, E> {
+        Ok(value.to_string())
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<String, E> {
+        Ok(String::new())
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<String, E> {
+        Ok(String::new())
+    }
+}
+
+/// Deserializes a numeric code, `None` for `null` and any value which isn't a non-negative integer.
+///
+/// Numeric strings are accepted as well. Used by the enums of the models, so unexpected values like
+/// negative numbers or non-numeric strings end up in their `Unknown` variant instead of failing the whole payload.
+///
+/// ```rust
+/// # use serde::Deserialize;
+/// # use deezer::serde_helpers;
+/// #[derive(Deserialize)]
+/// struct Entry {
+///     #[serde(deserialize_with = "serde_helpers::lenient_code")]
+///     status: Option<u64>,
+/// }
+///
+/// let entries: Vec<Entry> = serde_json::from_str(r#"[{"status": 2}, {"status": "3"}, {"status": -1}, {"status": "none"}, {"status": null}]"#).unwrap();
+/// let statuses: Vec<_> = entries.iter().map(|entry| entry.status).collect();
+///
+/// assert_eq!(statuses, vec![Some(2), Some(3), None, None, None]);
+/// ```
+pub fn lenient_code<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(LenientCodeVisitor)
+}
+
+struct LenientCodeVisitor;
+
+impl<'de> Visitor<'de> for LenientCodeVisitor {
+    type Value = Option<u64>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a code as number or string")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Option<u64>, E> {
+        Ok(Some(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Option<u64>, E> {
+        Ok(u64::try_from(value).ok())
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Option<u64>, E> {
+        Ok(IdVisitor.visit_f64::<E>(value).ok())
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Option<u64>, E> {
+        Ok(value.trim().parse().ok())
+    }
+
+    fn visit_bool<E: de::Error>(self, _: bool) -> Result<Option<u64>, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Option<u64>, E> {
+        Ok(None)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Option<u64>, E> {
+        Ok(None)
+    }
+}
+
+/// Deserializes an empty string as `None`.
+///
+/// Deezer often sends empty strings instead of omitting unknown values, e.g. for the previews of tracks without one.
+pub fn empty_string_as_none<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<String>::deserialize(deserializer)?;
+
+    Ok(value.filter(|value| !value.is_empty()))
+}
+
+/// (De)serializes a [`SystemTime`](std::time::SystemTime) as unix timestamp in seconds,
+/// use it with `#[serde(with = "serde_helpers::timestamp")]`.
+pub mod timestamp {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use serde::{Deserializer, Serializer};
+
+    /// Serializes the time as seconds since the unix epoch, times before the epoch are serialized as `0`
+    pub fn serialize<S: Serializer>(value: &SystemTime, serializer: S) -> Result<S::Ok, S::Error> {
+        let seconds = value.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+
+        serializer.serialize_u64(seconds)
+    }
+
+    /// Deserializes seconds since the unix epoch, sent as number or string
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SystemTime, D::Error> {
+        let seconds = super::id(deserializer)?;
+
+        Ok(UNIX_EPOCH + Duration::from_secs(seconds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Deserialize)]
+    struct Id(#[serde(deserialize_with = "super::id")] u64);
+
+    #[derive(Deserialize)]
+    struct LenientString(#[serde(deserialize_with = "super::lenient_string")] String);
+
+    #[derive(Deserialize)]
+    struct LenientCode(#[serde(deserialize_with = "super::lenient_code")] Option<u64>);
+
+    #[derive(Deserialize)]
+    struct EmptyStringAsNone(#[serde(deserialize_with = "super::empty_string_as_none")] Option<String>);
+
+    #[derive(Deserialize, Serialize, Debug, PartialEq)]
+    struct Timestamp(#[serde(with = "super::timestamp")] SystemTime);
+
+    fn parse<T: for<'de> Deserialize<'de>>(json: &str) -> Option<T> {
+        serde_json::from_str(json).ok()
+    }
+
+    #[test]
+    fn ids_are_numbers_or_strings() {
+        for json in ["3135556", "\"3135556\"", "\" 3135556 \"", "3135556.0"] {
+            assert_eq!(parse::<Id>(json).map(|id| id.0), Some(3135556), "{}", json);
+        }
+        for json in ["-1", "1.5", "\"\"", "\"abc\"", "null", "true", "[]", "{}"] {
+            assert!(parse::<Id>(json).is_none(), "{}", json);
+        }
+    }
+
+    #[test]
+    fn lenient_strings_accept_scalars() {
+        let cases = [("\"album\"", "album"), ("42", "42"), ("-1", "-1"), ("1.5", "1.5"), ("true", "true"), ("null", "")];
+        for (json, expected) in cases {
+            assert_eq!(parse::<LenientString>(json).map(|string| string.0).as_deref(), Some(expected), "{}", json);
+        }
+        for json in ["[]", "{}"] {
+            assert!(parse::<LenientString>(json).is_none(), "{}", json);
+        }
+    }
+
+    #[test]
+    fn lenient_codes_ignore_invalid_values() {
+        let cases = [
+            ("2", Some(2)),
+            ("\"3\"", Some(3)),
+            ("4.0", Some(4)),
+            ("-1", None),
+            ("1.5", None),
+            ("\"none\"", None),
+            ("true", None),
+            ("null", None),
+        ];
+        for (json, expected) in cases {
+            assert_eq!(parse::<LenientCode>(json).map(|code| code.0), Some(expected), "{}", json);
+        }
+    }
+
+    #[test]
+    fn empty_strings_are_none() {
+        let cases = [("\"\"", None), ("null", None), ("\"preview\"", Some("preview")), ("\" \"", Some(" "))];
+        for (json, expected) in cases {
+            assert_eq!(parse::<EmptyStringAsNone>(json).map(|string| string.0), Some(expected.map(String::from)), "{}", json);
+        }
+        assert!(parse::<EmptyStringAsNone>("42").is_none());
+    }
+
+    #[test]
+    fn timestamps_are_unix_seconds() {
+        let time = UNIX_EPOCH + Duration::from_secs(1600000000);
+        for json in ["1600000000", "\"1600000000\""] {
+            assert_eq!(parse::<Timestamp>(json), Some(Timestamp(time)), "{}", json);
+        }
+        assert_eq!(serde_json::to_string(&Timestamp(time)).unwrap(), "1600000000");
+        assert_eq!(serde_json::to_string(&Timestamp(UNIX_EPOCH - Duration::from_secs(1))).unwrap(), "0");
+        assert!(parse::<Timestamp>("-1").is_none());
+    }
+}
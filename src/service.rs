@@ -0,0 +1,87 @@
+//! Integration into [tower](https://docs.rs/tower) middleware stacks.
+//!
+//! The [`DeezerClient`] implements `tower::Service<DeezerRequest>`, so generic middleware like
+//! retries, timeouts or circuit breakers can wrap the requests to the api.
+#![warn(missing_docs)]
+use std::task::{Context, Poll};
+
+use futures::future::{BoxFuture, FutureExt};
+use reqwest::Method;
+use tower_service::Service;
+
+use crate::{DeezerClient, DeezerError, Params};
+
+/// A request to an api endpoint, answered with the parsed json body.
+///
+/// # Examples
+/// ```rust
+/// # use deezer::{DeezerClient, DeezerError, Params};
+/// # use deezer::service::DeezerRequest;
+/// # use tower_service::Service;
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), DeezerError> {
+/// let mut deezer = DeezerClient::new();
+/// let request = DeezerRequest::get("artist/27/top").with_params(Params::new().with("limit", 3u32));
+///
+/// let top = deezer.call(request).await?;
+/// # assert!(top["data"].is_array());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DeezerRequest {
+    /// The http method
+    pub method: Method,
+
+    /// The path of the endpoint relative to the api root, e.g. `artist/27/top`
+    pub path: String,
+
+    /// The query parameters
+    pub params: Params,
+}
+
+impl DeezerRequest {
+    /// Creates a request to the given endpoint without parameters.
+    pub fn new(method: Method, path: impl Into<String>) -> Self {
+        DeezerRequest {
+            method,
+            path: path.into(),
+            params: Params::new(),
+        }
+    }
+
+    /// Creates a `GET` request to the given endpoint.
+    pub fn get(path: impl Into<String>) -> Self {
+        DeezerRequest::new(Method::GET, path)
+    }
+
+    /// Sets the query parameters.
+    pub fn with_params(mut self, params: Params) -> Self {
+        self.params = params;
+        self
+    }
+}
+
+impl Service<DeezerRequest> for DeezerClient {
+    type Response = serde_json::Value;
+    type Error = DeezerError;
+    type Future = BoxFuture<'static, Result<serde_json::Value, DeezerError>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // the rate limit is awaited while sending the request
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: DeezerRequest) -> Self::Future {
+        let client = self.clone();
+        async move {
+            client
+                .request(request.method, &request.path)
+                .params(&request.params)
+                .json()
+                .await
+        }
+        .boxed()
+    }
+}
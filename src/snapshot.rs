@@ -0,0 +1,45 @@
+//! Storing fetched api objects in files, e.g. to run offline or to skip fetching static data on startup.
+//!
+//! Any serializable value can be stored, like a [`Catalog`](crate::catalog::Catalog),
+//! a [`LibrarySnapshot`](crate::sync::LibrarySnapshot) or a list of models.
+#![warn(missing_docs)]
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::Result;
+
+/// Writes `value` as json to the file at `path`, replacing an existing file.
+///
+/// # Examples
+/// ```rust
+/// # use deezer::snapshot;
+/// # use deezer::models::Genre;
+/// # fn main() -> Result<(), deezer::DeezerError> {
+/// let path = std::env::temp_dir().join("deezer-genres.json");
+/// let genres = vec![Genre::new(132, "Pop")];
+///
+/// snapshot::save(&path, &genres)?;
+/// let loaded: Vec<Genre> = snapshot::load(&path)?;
+///
+/// assert_eq!(loaded, genres);
+/// # Ok(())
+/// # }
+/// ```
+pub fn save<T: Serialize + ?Sized>(path: impl AsRef<Path>, value: &T) -> Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer(&mut writer, value)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Reads a value written by [`save()`] from the file at `path`.
+pub fn load<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T> {
+    let reader = BufReader::new(File::open(path)?);
+
+    Ok(serde_json::from_reader(reader)?)
+}
@@ -0,0 +1,507 @@
+//! Snapshots of a user's library and the differences between them.
+//!
+//! A [`LibrarySnapshot`] records the favorites and playlists of a user. Snapshots are serializable,
+//! so backup and mirror tools can store one and later compute a [`LibraryDiff`] against a fresh one.
+//! Long running applications can keep the favorites up to date with a [`FavoritesService`].
+//!
+//! Mirror tools can replay a diff onto the library of the authenticated user with [`LibraryDiff::apply()`].
+#![warn(missing_docs)]
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::fetch::{self, BatchResult};
+use crate::me::MeClient;
+use crate::models::{
+    AlbumId, AnyId, ArtistId, PartialAlbum, PartialArtist, PartialPlaylist, PartialTrack, PlaylistId, TrackId, UserId,
+};
+use crate::{DeezerClient, DeezerError, Result};
+
+/// Maximum number of changes [`LibraryDiff::apply()`] sends concurrently
+const APPLY_CONCURRENCY: usize = 4;
+
+/// The favorites and playlists of a user at one point in time.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct LibrarySnapshot {
+    /// Ids of the favorite tracks
+    pub tracks: BTreeSet<TrackId>,
+
+    /// Ids of the favorite albums
+    pub albums: BTreeSet<AlbumId>,
+
+    /// Ids of the favorite artists
+    pub artists: BTreeSet<ArtistId>,
+
+    /// Ids of the user's playlists with the checksum of their track list
+    pub playlists: BTreeMap<PlaylistId, String>,
+}
+
+impl LibrarySnapshot {
+    /// Captures the current library of the user with the given id.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # use deezer::sync::LibrarySnapshot;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new();
+    /// let previous = LibrarySnapshot::capture(&deezer, 2529).await?;
+    /// // ... some time later
+    /// let current = LibrarySnapshot::capture(&deezer, 2529).await?;
+    ///
+    /// let diff = current.diff(&previous);
+    /// for track in diff.tracks.added {
+    ///     println!("new favorite track {}", track);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn capture(client: &DeezerClient, user: impl Into<UserId>) -> Result<Self> {
+        let favorites = Favorites::fetch(client, user).await?;
+
+        Ok(favorites.snapshot())
+    }
+
+    /// Returns the changes from `previous` to this snapshot.
+    ///
+    /// ```rust
+    /// # use deezer::models::TrackId;
+    /// # use deezer::sync::LibrarySnapshot;
+    /// let mut previous = LibrarySnapshot::default();
+    /// previous.tracks.insert(TrackId::new(3135556));
+    /// let mut current = LibrarySnapshot::default();
+    /// current.tracks.insert(TrackId::new(3135553));
+    ///
+    /// let diff = current.diff(&previous);
+    /// assert_eq!(diff.tracks.added, vec![TrackId::new(3135553)]);
+    /// assert_eq!(diff.tracks.removed, vec![TrackId::new(3135556)]);
+    /// ```
+    pub fn diff(&self, previous: &LibrarySnapshot) -> LibraryDiff {
+        let current_playlists = self.playlists.keys().copied().collect();
+        let previous_playlists = previous.playlists.keys().copied().collect();
+        let changed_playlists = self
+            .playlists
+            .iter()
+            .filter(|(id, checksum)| previous.playlists.get(id).is_some_and(|previous| previous != *checksum))
+            .map(|(id, _)| *id)
+            .collect();
+
+        LibraryDiff {
+            tracks: CollectionDiff::between(&previous.tracks, &self.tracks),
+            albums: CollectionDiff::between(&previous.albums, &self.albums),
+            artists: CollectionDiff::between(&previous.artists, &self.artists),
+            playlists: CollectionDiff::between(&previous_playlists, &current_playlists),
+            changed_playlists,
+        }
+    }
+}
+
+/// The favorites and playlists of a user.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+#[non_exhaustive]
+pub struct Favorites {
+    /// The favorite tracks
+    pub tracks: Vec<PartialTrack>,
+
+    /// The favorite albums
+    pub albums: Vec<PartialAlbum>,
+
+    /// The favorite artists
+    pub artists: Vec<PartialArtist>,
+
+    /// The user's playlists
+    pub playlists: Vec<PartialPlaylist>,
+}
+
+impl Favorites {
+    /// Fetches the favorites of the user with the given id.
+    pub async fn fetch(client: &DeezerClient, user: impl Into<UserId>) -> Result<Self> {
+        let user = user.into();
+        let (tracks, albums, artists, playlists) = futures::try_join!(
+            client.user_favorite_tracks(user),
+            client.user_favorite_albums(user),
+            client.user_favorite_artists(user),
+            client.user_playlists(user),
+        )?;
+
+        Ok(Favorites {
+            tracks,
+            albums,
+            artists,
+            playlists,
+        })
+    }
+
+    /// Returns the ids and playlist checksums of the favorites.
+    pub fn snapshot(&self) -> LibrarySnapshot {
+        LibrarySnapshot {
+            tracks: self.tracks.iter().map(|track| track.id).collect(),
+            albums: self.albums.iter().map(|album| album.id).collect(),
+            artists: self.artists.iter().map(|artist| artist.id).collect(),
+            playlists: self
+                .playlists
+                .iter()
+                .map(|playlist| (playlist.id, playlist.checksum.clone().unwrap_or_default()))
+                .collect(),
+        }
+    }
+}
+
+/// The checksums deezer sends along with the lists of favorites of a user.
+///
+/// `None` for lists without a checksum, which are always considered changed.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct FavoritesChecksums {
+    tracks: Option<String>,
+    albums: Option<String>,
+    artists: Option<String>,
+    playlists: Option<String>,
+}
+
+impl FavoritesChecksums {
+    async fn fetch(client: &DeezerClient, user: UserId) -> Result<Self> {
+        let user = user.to_string();
+        let (tracks, albums, artists, playlists) = futures::try_join!(
+            client.get_checksum(client.api_url(&["user", &user, "tracks"])),
+            client.get_checksum(client.api_url(&["user", &user, "albums"])),
+            client.get_checksum(client.api_url(&["user", &user, "artists"])),
+            client.get_checksum(client.api_url(&["user", &user, "playlists"])),
+        )?;
+
+        Ok(FavoritesChecksums {
+            tracks,
+            albums,
+            artists,
+            playlists,
+        })
+    }
+
+    /// Returns `true` when all lists have a checksum and none of them differs from `previous`.
+    fn unchanged_since(&self, previous: &FavoritesChecksums) -> bool {
+        let complete = self.tracks.is_some() && self.albums.is_some() && self.artists.is_some() && self.playlists.is_some();
+
+        complete && self == previous
+    }
+}
+
+/// Keeps the [`Favorites`] of a user up to date in a background task.
+///
+/// Every refresh first requests the checksums of the lists of favorites and only fetches the
+/// favorites when one of them changed. Subscribers are only notified when the favorites changed,
+/// which is detected by comparing the ids and the checksums of the playlists. Failed refreshes
+/// are logged and the previous favorites are kept. The task is stopped when the service is dropped.
+///
+/// # Examples
+/// ```rust,no_run
+/// # use std::time::Duration;
+/// # use deezer::DeezerClient;
+/// # use deezer::sync::FavoritesService;
+/// # #[tokio::main]
+/// # async fn main() {
+/// let service = FavoritesService::spawn(DeezerClient::new(), 2529, Duration::from_secs(300));
+/// let mut favorites = service.subscribe();
+/// while favorites.changed().await.is_ok() {
+///     if let Some(favorites) = favorites.borrow().as_ref() {
+///         println!("{} favorite tracks", favorites.tracks.len());
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FavoritesService {
+    favorites: watch::Receiver<Option<Arc<Favorites>>>,
+    task: JoinHandle<()>,
+}
+
+impl FavoritesService {
+    /// Spawns a task on the current tokio runtime refreshing the favorites every `period`.
+    pub fn spawn(client: DeezerClient, user: impl Into<UserId>, period: Duration) -> Self {
+        let user = user.into();
+        let (sender, favorites) = watch::channel(None);
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            let mut checksums = None;
+            let mut snapshot = None;
+            loop {
+                ticker.tick().await;
+                let current_checksums = match FavoritesChecksums::fetch(&client, user).await {
+                    Ok(current) => current,
+                    Err(err) => {
+                        log::warn!("Refreshing the favorites of user {} failed: {}", user, err);
+                        continue;
+                    }
+                };
+                if checksums.as_ref().is_some_and(|previous| current_checksums.unchanged_since(previous)) {
+                    continue;
+                }
+                let current = match Favorites::fetch(&client, user).await {
+                    Ok(current) => current,
+                    Err(err) => {
+                        log::warn!("Refreshing the favorites of user {} failed: {}", user, err);
+                        continue;
+                    }
+                };
+                checksums = Some(current_checksums);
+                let current_snapshot = current.snapshot();
+                if snapshot.as_ref() == Some(&current_snapshot) {
+                    continue;
+                }
+                snapshot = Some(current_snapshot);
+                if sender.send(Some(Arc::new(current))).is_err() {
+                    // all receivers are gone
+                    break;
+                }
+            }
+        });
+
+        FavoritesService { favorites, task }
+    }
+
+    /// Returns a receiver which is notified about every change of the favorites.
+    ///
+    /// The value is `None` until the first refresh succeeded.
+    pub fn subscribe(&self) -> watch::Receiver<Option<Arc<Favorites>>> {
+        self.favorites.clone()
+    }
+
+    /// Returns the latest favorites.
+    pub fn latest(&self) -> Option<Arc<Favorites>> {
+        self.favorites.borrow().clone()
+    }
+}
+
+impl Drop for FavoritesService {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Items added to and removed from a collection.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CollectionDiff<T> {
+    /// Items which are new in the collection
+    pub added: Vec<T>,
+
+    /// Items which are no longer in the collection
+    pub removed: Vec<T>,
+}
+
+impl<T: Ord + Copy> CollectionDiff<T> {
+    fn between(previous: &BTreeSet<T>, current: &BTreeSet<T>) -> Self {
+        CollectionDiff {
+            added: current.difference(previous).copied().collect(),
+            removed: previous.difference(current).copied().collect(),
+        }
+    }
+}
+
+impl<T> CollectionDiff<T> {
+    /// Returns `true` when the collection didn't change.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// The changes between two [`LibrarySnapshot`]s.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LibraryDiff {
+    /// Changes of the favorite tracks
+    pub tracks: CollectionDiff<TrackId>,
+
+    /// Changes of the favorite albums
+    pub albums: CollectionDiff<AlbumId>,
+
+    /// Changes of the favorite artists
+    pub artists: CollectionDiff<ArtistId>,
+
+    /// Changes of the user's playlists
+    pub playlists: CollectionDiff<PlaylistId>,
+
+    /// Playlists in both snapshots whose track list changed
+    pub changed_playlists: Vec<PlaylistId>,
+}
+
+impl LibraryDiff {
+    /// Returns `true` when nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.tracks.is_empty()
+            && self.albums.is_empty()
+            && self.artists.is_empty()
+            && self.playlists.is_empty()
+            && self.changed_playlists.is_empty()
+    }
+
+    /// Returns the added and removed favorites and playlists, additions first.
+    ///
+    /// The [`changed_playlists`](LibraryDiff::changed_playlists) aren't included.
+    ///
+    /// ```rust
+    /// # use deezer::models::{AlbumId, TrackId};
+    /// # use deezer::sync::{LibraryChange, LibrarySnapshot};
+    /// let mut previous = LibrarySnapshot::default();
+    /// previous.albums.insert(AlbumId::new(302127));
+    /// let mut current = LibrarySnapshot::default();
+    /// current.tracks.insert(TrackId::new(3135553));
+    ///
+    /// assert_eq!(current.diff(&previous).changes(), vec![
+    ///     LibraryChange::Added(TrackId::new(3135553).into()),
+    ///     LibraryChange::Removed(AlbumId::new(302127).into()),
+    /// ]);
+    /// ```
+    pub fn changes(&self) -> Vec<LibraryChange> {
+        fn ids<T: Copy + Into<AnyId>>(ids: &[T]) -> impl Iterator<Item = AnyId> + '_ {
+            ids.iter().map(|id| (*id).into())
+        }
+
+        let added = ids(&self.tracks.added)
+            .chain(ids(&self.albums.added))
+            .chain(ids(&self.artists.added))
+            .chain(ids(&self.playlists.added))
+            .map(LibraryChange::Added);
+        let removed = ids(&self.tracks.removed)
+            .chain(ids(&self.albums.removed))
+            .chain(ids(&self.artists.removed))
+            .chain(ids(&self.playlists.removed))
+            .map(LibraryChange::Removed);
+
+        added.chain(removed).collect()
+    }
+
+    /// Replays the [`changes()`](LibraryDiff::changes) onto the favorites of the authenticated user,
+    /// e.g. to mirror the library of another account.
+    ///
+    /// Added playlists are subscribed to and removed ones unsubscribed from, changes of their
+    /// track lists aren't applied. Failing changes don't abort the others, they are reported in
+    /// [`BatchResult::failed`]. Requires the [`ManageLibrary`](crate::auth::Permission::ManageLibrary)
+    /// and, for removals, the [`DeleteLibrary`](crate::auth::Permission::DeleteLibrary) permission.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// # use deezer::{DeezerClient, DeezerError};
+    /// # use deezer::sync::LibrarySnapshot;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), DeezerError> {
+    /// let deezer = DeezerClient::new().with_access_token("access-token");
+    /// let source = LibrarySnapshot::capture(&deezer, 2529).await?;
+    /// let mirror = LibrarySnapshot::capture(&deezer, deezer.me().await?.id).await?;
+    ///
+    /// let result = source.diff(&mirror).apply(&deezer.me_client()).await;
+    /// for (change, err) in result.failed {
+    ///     println!("{:?} failed: {}", change, err);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn apply(&self, me: &MeClient) -> BatchResult<LibraryChange, LibraryChange> {
+        fetch::settled(self.changes(), APPLY_CONCURRENCY, |change| async move {
+            match change {
+                LibraryChange::Added(AnyId::Track(id)) => me.add_favorite_track(id).await?,
+                LibraryChange::Added(AnyId::Album(id)) => me.add_favorite_album(id).await?,
+                LibraryChange::Added(AnyId::Artist(id)) => me.add_favorite_artist(id).await?,
+                LibraryChange::Added(AnyId::Playlist(id)) => me.add_favorite_playlist(id).await?,
+                LibraryChange::Removed(AnyId::Track(id)) => me.remove_favorite_track(id).await?,
+                LibraryChange::Removed(AnyId::Album(id)) => me.remove_favorite_album(id).await?,
+                LibraryChange::Removed(AnyId::Artist(id)) => me.remove_favorite_artist(id).await?,
+                LibraryChange::Removed(AnyId::Playlist(id)) => me.remove_favorite_playlist(id).await?,
+                // users are never part of a library
+                LibraryChange::Added(id) | LibraryChange::Removed(id) => return Err(DeezerError::InvalidId(id.to_string())),
+            }
+
+            Ok(change)
+        })
+        .await
+    }
+}
+
+/// A favorite or playlist which was added to or removed from a library, see [`LibraryDiff::changes()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LibraryChange {
+    /// The object is new in the library
+    Added(AnyId),
+
+    /// The object is no longer in the library
+    Removed(AnyId),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::MockServer;
+
+    const LISTS: [&str; 4] = ["tracks", "albums", "artists", "playlists"];
+
+    fn full_fetches(server: &MockServer, list: &str) -> usize {
+        server
+            .requests_to(&format!("/user/2529/{}", list))
+            .iter()
+            .filter(|request| request.query("limit").is_none())
+            .count()
+    }
+
+    async fn wait_for_favorites(service: &FavoritesService) {
+        let mut favorites = service.subscribe();
+        while favorites.borrow().is_none() {
+            favorites.changed().await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn unchanged_checksums_skip_the_favorites() {
+        let server = MockServer::start().await;
+        for list in LISTS {
+            server.respond(&format!("/user/2529/{}", list), r#"{"data": [], "checksum": "unchanged", "total": 0}"#);
+        }
+        let client = DeezerClient::new().with_base_url(server.url());
+
+        let service = FavoritesService::spawn(client, 2529, Duration::from_millis(10));
+        wait_for_favorites(&service).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        for list in LISTS {
+            assert!(server.requests_to(&format!("/user/2529/{}", list)).len() > 2);
+            assert_eq!(full_fetches(&server, list), 1, "{} were fetched again", list);
+        }
+    }
+
+    #[tokio::test]
+    async fn changed_checksums_refetch_the_favorites() {
+        let server = MockServer::start().await;
+        server
+            .respond("/user/2529/tracks", r#"{"data": [], "checksum": "first", "total": 0}"#)
+            .respond("/user/2529/tracks", r#"{"data": [], "checksum": "first", "total": 0}"#)
+            .respond("/user/2529/tracks", r#"{"data": [], "checksum": "second", "total": 0}"#);
+        for list in &LISTS[1..] {
+            server.respond(&format!("/user/2529/{}", list), r#"{"data": [], "checksum": "unchanged", "total": 0}"#);
+        }
+        let client = DeezerClient::new().with_base_url(server.url());
+
+        let service = FavoritesService::spawn(client, 2529, Duration::from_millis(10));
+        wait_for_favorites(&service).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(full_fetches(&server, "tracks"), 2);
+    }
+
+    #[tokio::test]
+    async fn missing_checksums_always_fetch_the_favorites() {
+        let server = MockServer::start().await;
+        for list in LISTS {
+            server.respond(&format!("/user/2529/{}", list), r#"{"data": [], "total": 0}"#);
+        }
+        let client = DeezerClient::new().with_base_url(server.url());
+
+        let service = FavoritesService::spawn(client, 2529, Duration::from_millis(10));
+        wait_for_favorites(&service).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert!(full_fetches(&server, "tracks") > 1);
+    }
+}
@@ -0,0 +1,124 @@
+//! Conversion of api objects into audio tag metadata.
+//!
+//! [`TagMetadata`] mirrors the fields common to id3, vorbis comments and mp4 atoms,
+//! so taggers only need to map it onto the tag library of their choice.
+#![warn(missing_docs)]
+use crate::models::{Album, ImageSize, PartialTrack, Track};
+
+/// Tag fields of a track or album.
+///
+/// Fields the api doesn't provide for the converted object are left as `None`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use deezer::models::*;
+/// # use deezer::tags::TagMetadata;
+/// let mut track = Track::new(3135556, "Harder, Better, Faster, Stronger");
+/// track.artist = TrackArtist::new(27, "Daft Punk");
+/// track.album = TrackAlbum::new(302127, "Discovery");
+/// track.track_position_in_album = 4;
+/// track.release_date = "2001-03-07".to_string();
+///
+/// let tags = TagMetadata::from(&track);
+/// assert_eq!(tags.artist.as_deref(), Some("Daft Punk"));
+/// assert_eq!(tags.album.as_deref(), Some("Discovery"));
+/// assert_eq!(tags.track_number, Some(4));
+/// assert_eq!(tags.year, Some(2001));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct TagMetadata {
+    /// Title of the track
+    pub title: Option<String>,
+
+    /// Main artist of the track
+    pub artist: Option<String>,
+
+    /// Title of the album
+    pub album: Option<String>,
+
+    /// Main artist of the album
+    pub album_artist: Option<String>,
+
+    /// Position of the track on its disk
+    pub track_number: Option<u64>,
+
+    /// Number of tracks on the album
+    pub track_total: Option<u64>,
+
+    /// Disk of the album the track is on
+    pub disk_number: Option<u64>,
+
+    /// Year of the release
+    pub year: Option<i32>,
+
+    /// Record label of the album
+    pub label: Option<String>,
+
+    /// Genre of the album
+    pub genre: Option<String>,
+
+    /// International Standard Recording Code of the track
+    pub isrc: Option<String>,
+
+    /// Url of the largest cover
+    pub cover_url: Option<String>,
+}
+
+impl TagMetadata {
+    /// Returns the tags of a track of the given album.
+    ///
+    /// Combines the track fields with the album fields which [`PartialTrack`] doesn't provide.
+    pub fn for_album_track(track: &PartialTrack, album: &Album) -> Self {
+        TagMetadata {
+            title: Some(track.title.clone()),
+            artist: Some(track.artist.name.clone()),
+            ..TagMetadata::from(album)
+        }
+    }
+}
+
+impl From<&Track> for TagMetadata {
+    fn from(track: &Track) -> Self {
+        TagMetadata {
+            title: Some(track.title.clone()),
+            artist: Some(track.artist.name.clone()),
+            album: Some(track.album.title.clone()),
+            track_number: non_zero(track.track_position_in_album),
+            disk_number: non_zero(track.album_disk_number),
+            year: parse_year(&track.release_date),
+            isrc: non_empty(&track.isrc),
+            cover_url: track.album.image_url(ImageSize::Xl).map(String::from),
+            ..TagMetadata::default()
+        }
+    }
+}
+
+impl From<&Album> for TagMetadata {
+    fn from(album: &Album) -> Self {
+        TagMetadata {
+            album: Some(album.title.clone()),
+            album_artist: Some(album.artist.name.clone()),
+            track_total: non_zero(album.nb_tracks),
+            year: parse_year(&album.release_date),
+            label: non_empty(&album.label),
+            genre: album.genres.first().map(|genre| genre.name.clone()),
+            cover_url: non_empty(album.image_url(ImageSize::Xl)),
+            ..TagMetadata::default()
+        }
+    }
+}
+
+fn non_zero(value: u64) -> Option<u64> {
+    Some(value).filter(|value| *value != 0)
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    Some(value).filter(|value| !value.is_empty()).map(String::from)
+}
+
+/// Parses the year of a `YYYY-MM-DD` date, Deezer uses `0000-00-00` for unknown dates.
+fn parse_year(date: &str) -> Option<i32> {
+    date.get(..4)?.parse().ok().filter(|year| *year != 0)
+}
@@ -0,0 +1,144 @@
+//! Polling of api objects, yielding an event whenever they change.
+//!
+//! The watchers return [`Stream`]s which poll on every tick of the given period.
+//! Failed polls are yielded as errors, polling continues afterwards.
+//! The streams require a tokio runtime.
+#![warn(missing_docs)]
+use std::future::Future;
+use std::time::Duration;
+
+use futures::stream::{self, Stream};
+use tokio::time::MissedTickBehavior;
+
+use crate::models::{Chart, Playlist, PlaylistId, UserId};
+use crate::sync::{LibraryDiff, LibrarySnapshot};
+use crate::{DeezerClient, Result};
+
+/// A change of a watched object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change<T> {
+    /// The state before the change, `None` for the first poll
+    pub previous: Option<T>,
+
+    /// The state after the change
+    pub current: T,
+}
+
+impl Change<LibrarySnapshot> {
+    /// Returns the differences between the previous and current library.
+    ///
+    /// For the first poll the whole library is reported as added.
+    pub fn diff(&self) -> LibraryDiff {
+        match &self.previous {
+            Some(previous) => self.current.diff(previous),
+            None => self.current.diff(&LibrarySnapshot::default()),
+        }
+    }
+}
+
+/// Calls `fetch` every `period` and yields a [`Change`] whenever the `key` of the result changes.
+///
+/// The first result is always yielded.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// # use futures::StreamExt;
+/// # use deezer::{watch, DeezerClient, DeezerError};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), DeezerError> {
+/// let deezer = DeezerClient::new();
+/// let radios = watch::poll(Duration::from_secs(600), || deezer.radios(), |radios| radios.len());
+/// futures::pin_mut!(radios);
+///
+/// let change = radios.next().await.unwrap()?;
+/// assert!(change.previous.is_none());
+/// # Ok(())
+/// # }
+/// ```
+pub fn poll<T, K, F, Fut, G>(period: Duration, fetch: F, key: G) -> impl Stream<Item = Result<Change<T>>>
+where
+    T: Clone,
+    K: PartialEq,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+    G: Fn(&T) -> K,
+{
+    poll_unchanged(period, fetch, move |previous, current| key(previous) == key(current))
+}
+
+/// Like [`poll()`], but yields a [`Change`] unless `unchanged` returns `true` for the previous and current result.
+fn poll_unchanged<T, F, Fut, U>(period: Duration, fetch: F, unchanged: U) -> impl Stream<Item = Result<Change<T>>>
+where
+    T: Clone,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+    U: Fn(&T, &T) -> bool,
+{
+    let mut ticker = tokio::time::interval(period);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    stream::unfold((ticker, fetch, unchanged, None), |(mut ticker, mut fetch, unchanged, mut previous)| async move {
+        loop {
+            ticker.tick().await;
+            let current = match fetch().await {
+                Ok(current) => current,
+                Err(err) => return Some((Err(err), (ticker, fetch, unchanged, previous))),
+            };
+            if previous.as_ref().is_some_and(|previous| unchanged(previous, &current)) {
+                continue;
+            }
+            let change = Change {
+                previous: previous.replace(current.clone()),
+                current,
+            };
+            return Some((Ok(change), (ticker, fetch, unchanged, previous)));
+        }
+    })
+}
+
+/// Watches the tracks of a playlist, detected by its [`checksum`](Playlist::checksum).
+///
+/// `current` is `None` once the playlist has been deleted.
+pub fn playlist(client: &DeezerClient, id: impl Into<PlaylistId>,
+                period: Duration) -> impl Stream<Item = Result<Change<Option<Playlist>>>> {
+    let client = client.clone();
+    let id = id.into();
+    poll(
+        period,
+        move || {
+            let client = client.clone();
+            async move { client.playlist(id).await }
+        },
+        |playlist| playlist.as_ref().map(|playlist| playlist.checksum.clone()),
+    )
+}
+
+/// Watches the charts.
+pub fn chart(client: &DeezerClient, period: Duration) -> impl Stream<Item = Result<Change<Chart>>> {
+    let client = client.clone();
+    poll_unchanged(
+        period,
+        move || {
+            let client = client.clone();
+            async move { client.charts().await }
+        },
+        Chart::eq,
+    )
+}
+
+/// Watches the favorites and playlists of a user, use [`Change::diff()`] to get the changes.
+pub fn library(client: &DeezerClient, user: impl Into<UserId>,
+               period: Duration) -> impl Stream<Item = Result<Change<LibrarySnapshot>>> {
+    let client = client.clone();
+    let user = user.into();
+    poll_unchanged(
+        period,
+        move || {
+            let client = client.clone();
+            async move { LibrarySnapshot::capture(&client, user).await }
+        },
+        LibrarySnapshot::eq,
+    )
+}